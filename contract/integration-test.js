@@ -181,7 +181,8 @@ async function main() {
     const eventTitle = 'Test Event - Integration Test';
     const eventDate = 2024; // Year format like in the test script
     const eventDescription = 'This is a test event for integration testing';
-    const evidenceSources = ['https://example.com/evidence1', 'https://example.com/evidence2'];
+    const evidenceSources = ['https://example.com/evidence1', 'https://example.com/evidence2']
+      .map((citation) => ({ kind: 'Url', citation, contentHash: null, archivedCopy: null }));
     
     try {
       await sendTransaction(
@@ -350,7 +351,7 @@ async function main() {
         'Canonical Timeline Test Event',
         2025,
         'Testing 75% threshold for canonical timeline',
-        ['https://example.com/evidence']
+        [{ kind: 'Url', citation: 'https://example.com/evidence', contentHash: null, archivedCopy: null }]
       );
       logSuccess('Test event submitted');
       testResults.passed++;
@@ -416,7 +417,7 @@ async function main() {
         'Alternative Timeline Test Event',
         2023,
         'Testing 25% threshold for alternative timeline',
-        ['https://example.com/evidence']
+        [{ kind: 'Url', citation: 'https://example.com/evidence', contentHash: null, archivedCopy: null }]
       );
       logSuccess('Test event submitted');
       testResults.passed++;
@@ -472,7 +473,7 @@ async function main() {
     // Test 7: Query Events by Timeline
     logSection('🔍 Test 7: Query Events by Timeline');
     
-    const timelines = ['Canonical', 'Disputed', 'Alternative'];
+    const timelines = ['Pending', 'Canonical', 'Disputed', 'Alternative'];
     for (const timeline of timelines) {
       logTest(`Querying ${timeline} timeline`);
       try {
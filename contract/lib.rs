@@ -4,7 +4,9 @@
 mod history_protocol {
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
-    use ink::storage::Mapping;
+    use ink::primitives::KeyComposer;
+    use ink::storage::traits::ManualKey;
+    use ink::storage::{Lazy, Mapping};
 
     /// Defines the timeline categories for historical events
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
@@ -16,6 +18,121 @@ mod history_protocol {
         Canonical,
         Disputed,
         Alternative,
+        /// Not enough votes yet to place the event on one of the other timelines
+        Pending,
+    }
+
+    /// What kind of material an evidence source points to
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum EvidenceKind {
+        /// Web page; the citation must be an http(s) URL
+        Url,
+        /// Book; the citation must be a valid ISBN-10 or ISBN-13
+        Isbn,
+        /// Paper or dataset; the citation must be a DOI such as `10.1000/xyz123`
+        Doi,
+        /// Record held by an archive or library, cited in free text
+        Archive,
+        /// Content on IPFS; the citation must be a CIDv0 or base32 CIDv1
+        IpfsCid,
+        /// Original source document, cited in free text
+        PrimaryDocument,
+    }
+
+    /// A source backing a historical event
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Evidence {
+        pub kind: EvidenceKind,
+        /// URL, ISBN, DOI, CID or free-text citation, depending on `kind`
+        pub citation: String,
+        /// BLAKE2-256 hash of the cited material, so anyone can check it hasn't changed
+        pub content_hash: Option<[u8; 32]>,
+        /// Where an archived copy can be found, e.g. a Wayback Machine URL or an IPFS CID
+        pub archived_copy: Option<String>,
+    }
+
+    /// A voter's judgement of a single evidence source
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum EvidenceRating {
+        /// Counts 100 towards the source's score
+        Credible,
+        /// Counts 50 towards the source's score
+        Weak,
+        /// Counts 0 towards the source's score
+        Fabricated,
+    }
+
+    impl EvidenceRating {
+        /// Contribution of one rating to a source's score, in percent
+        fn score(&self) -> Balance {
+            match self {
+                EvidenceRating::Credible => 100,
+                EvidenceRating::Weak => 50,
+                EvidenceRating::Fabricated => 0,
+            }
+        }
+    }
+
+    /// A rating together with the vote weight it counts with
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct WeightedRating {
+        pub rating: EvidenceRating,
+        pub weight: Balance,
+    }
+
+    /// Rating totals for one evidence source, weighted by the raters' votes on the event
+    #[derive(Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct EvidenceTally {
+        pub credible: Balance,
+        pub weak: Balance,
+        pub fabricated: Balance,
+    }
+
+    impl EvidenceTally {
+        fn count_mut(&mut self, rating: EvidenceRating) -> &mut Balance {
+            match rating {
+                EvidenceRating::Credible => &mut self.credible,
+                EvidenceRating::Weak => &mut self.weak,
+                EvidenceRating::Fabricated => &mut self.fabricated,
+            }
+        }
+
+        /// Total weight of the ratings cast on the source
+        pub fn total(&self) -> Balance {
+            self.credible.saturating_add(self.weak).saturating_add(self.fabricated)
+        }
+
+        /// Average rating in percent, or `None` if the source is unrated
+        pub fn score(&self) -> Option<u8> {
+            let total = self.total();
+            if total == 0 {
+                return None;
+            }
+            let points = self.credible.saturating_mul(EvidenceRating::Credible.score())
+                .saturating_add(self.weak.saturating_mul(EvidenceRating::Weak.score()))
+                .saturating_add(self.fabricated.saturating_mul(EvidenceRating::Fabricated.score()));
+            points.checked_div(total).and_then(|score| u8::try_from(score).ok())
+        }
     }
 
     /// Represents a historical event with all metadata
@@ -29,14 +146,26 @@ mod history_protocol {
         pub title: String,
         pub date: u64,
         pub description: String,
-        pub evidence_sources: Vec<String>,
+        pub evidence_sources: Vec<Evidence>,
         pub tags: Vec<String>,
         pub submitter: AccountId,
         pub timeline: Timeline,
         pub consensus_score: u8,
-        pub support_votes: u32,
-        pub challenge_votes: u32,
+        /// Total weight of support votes
+        pub support_votes: Balance,
+        /// Total weight of challenge votes
+        pub challenge_votes: Balance,
+        /// Number of accounts that currently have a vote on this event
+        pub voter_count: u32,
         pub created_at: u64,
+        /// Set by a moderator to keep abusive content out of listings
+        pub hidden: bool,
+        /// Current revision number, 0 for the content as submitted
+        pub revision: u32,
+        /// Latest revision that changed the substance; votes cast before it are outdated
+        pub substantive_revision: u32,
+        /// Average rating of the rated evidence sources in percent, `None` until any source is rated
+        pub evidence_quality_score: Option<u8>,
     }
 
     /// Represents a vote on a historical event
@@ -50,994 +179,8032 @@ mod history_protocol {
         pub event_id: u64,
         pub support: bool,
         pub timestamp: u64,
+        /// Weight counted towards the event's support or challenge total
+        pub weight: Balance,
+        /// Funds held by the contract for this vote (token-weighted mode only)
+        pub stake: Balance,
+        /// Lock level chosen for the stake; multiplies the vote's weight
+        pub conviction: Conviction,
+        /// Timestamp until which the stake cannot be withdrawn
+        pub locked_until: u64,
+        /// Revision of the event the vote was cast on
+        pub revision: u32,
     }
 
-    /// Contract errors
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        EventNotFound,
-        AlreadyVoted,
-        InvalidEventData,
+    /// A historical event in the layout written by version 0 code
+    #[derive(Debug, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct LegacyEvent {
+        pub id: u64,
+        pub title: String,
+        pub date: u64,
+        pub description: String,
+        pub evidence_sources: Vec<String>,
+        pub tags: Vec<String>,
+        pub submitter: AccountId,
+        pub timeline: Timeline,
+        pub consensus_score: u8,
+        pub support_votes: u32,
+        pub challenge_votes: u32,
+        pub created_at: u64,
     }
 
-    /// The main contract storage
-    #[ink(storage)]
-    pub struct HistoryProtocol {
-        events: Mapping<u64, HistoricalEvent>,
-        event_count: u64,
-        votes: Mapping<(u64, AccountId), Vote>,
-        timeline_events: Mapping<Timeline, Vec<u64>>,
-        user_events: Mapping<AccountId, Vec<u64>>,
+    /// Version 0 votes on a converted event that `migrate_votes` has not moved yet
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct LegacyVoteTally {
+        pub support: u32,
+        pub challenge: u32,
     }
 
-    impl HistoryProtocol {
-        /// Constructor that initializes the contract
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            Self {
-                events: Mapping::default(),
-                event_count: 0,
-                votes: Mapping::default(),
-                timeline_events: Mapping::default(),
-                user_events: Mapping::default(),
+    /// A vote in the layout written by version 0 code
+    #[derive(Debug, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct LegacyVote {
+        pub voter: AccountId,
+        pub event_id: u64,
+        pub support: bool,
+        pub timestamp: u64,
+    }
+
+    /// Lock level of a token-weighted vote, modelled on Polkadot's conviction voting
+    ///
+    /// Weights are counted in tenths of the stake: `None` counts 0.1x with no lock,
+    /// `LockedNx` counts Nx and locks the stake for 2^(N-1) lock periods.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Conviction {
+        None,
+        Locked1x,
+        Locked2x,
+        Locked3x,
+        Locked4x,
+        Locked5x,
+        Locked6x,
+    }
+
+    impl Conviction {
+        /// Weight multiplier in tenths
+        fn multiplier(&self) -> Balance {
+            match self {
+                Conviction::None => 1,
+                Conviction::Locked1x => 10,
+                Conviction::Locked2x => 20,
+                Conviction::Locked3x => 30,
+                Conviction::Locked4x => 40,
+                Conviction::Locked5x => 50,
+                Conviction::Locked6x => 60,
             }
         }
 
-        /// Submits a new historical event to the protocol
-        /// 
-        /// # Arguments
-        /// * `title` - The title of the historical event
-        /// * `date` - Unix timestamp of when the event occurred
-        /// * `description` - Detailed description of the event
-        /// * `evidence_sources` - Vector of evidence URLs or references
-        /// * `tags` - Vector of category tags (minimum 1, maximum 5)
-        /// 
-        /// # Returns
-        /// * `Ok(u64)` - The unique event ID if successful
-        /// * `Err(Error::InvalidEventData)` - If validation fails
-        #[ink(message)]
-        pub fn submit_event(
-            &mut self,
-            title: String,
-            date: u64,
-            description: String,
-            evidence_sources: Vec<String>,
-            tags: Vec<String>,
-        ) -> Result<u64, Error> {
-            // Validate required fields
-            if title.is_empty() {
-                return Err(Error::InvalidEventData);
-            }
-            if description.is_empty() {
-                return Err(Error::InvalidEventData);
-            }
-            if evidence_sources.is_empty() {
-                return Err(Error::InvalidEventData);
-            }
-            
-            // Validate tags: require at least 1 tag, maximum 5 tags
-            if tags.is_empty() {
-                return Err(Error::InvalidEventData);
-            }
-            if tags.len() > 5 {
-                return Err(Error::InvalidEventData);
+        /// Number of lock periods the stake is held for
+        fn lock_periods(&self) -> u64 {
+            match self {
+                Conviction::None => 0,
+                Conviction::Locked1x => 1,
+                Conviction::Locked2x => 2,
+                Conviction::Locked3x => 4,
+                Conviction::Locked4x => 8,
+                Conviction::Locked5x => 16,
+                Conviction::Locked6x => 32,
             }
+        }
+    }
 
-            // Generate unique event ID
-            let event_id = self.generate_event_id();
-            
-            // Get current timestamp and caller
-            let caller = self.env().caller();
-            let now = self.env().block_timestamp();
+    /// Stake from a retracted vote that is still locked
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct StakeLock {
+        pub amount: Balance,
+        pub locked_until: u64,
+    }
 
-            // Create HistoricalEvent with initial Disputed timeline and 0 consensus score
-            let event = HistoricalEvent {
-                id: event_id,
-                title,
-                date,
-                description,
-                evidence_sources,
-                tags,
-                submitter: caller,
-                timeline: Timeline::Disputed,
-                consensus_score: 0,
-                support_votes: 0,
-                challenge_votes: 0,
-                created_at: now,
-            };
+    /// How much each vote counts, chosen at instantiation
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum VotingMode {
+        /// Every account's vote has weight 1
+        OneAccountOneVote,
+        /// A vote's weight is the value transferred with it times its conviction, held until the vote is retracted and its lock expires
+        TokenWeighted,
+        /// Accounts spend periodic vote credits; casting k votes costs k² credits
+        Quadratic,
+    }
 
-            // Store event in events mapping
-            self.events.insert(event_id, &event);
+    /// An account's quadratic voting credit balance
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct VoteCredits {
+        /// Credits left in the current period
+        pub balance: u32,
+        /// Credit period the balance belongs to
+        pub period: u64,
+    }
 
-            // Store event ID in timeline_events mapping
-            let mut timeline_events = self.timeline_events.get(Timeline::Disputed).unwrap_or_default();
-            timeline_events.push(event_id);
-            self.timeline_events.insert(Timeline::Disputed, &timeline_events);
+    /// A record of credits spent on a quadratic vote
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct CreditSpend {
+        pub event_id: u64,
+        /// Amendment the credits were spent voting on, `None` for a vote on the event itself
+        pub amendment_id: Option<u64>,
+        pub support: bool,
+        pub votes: u32,
+        pub credits: u32,
+        pub timestamp: u64,
+    }
 
-            // Track event in user_events mapping
-            let mut user_events = self.user_events.get(caller).unwrap_or_default();
-            user_events.push(event_id);
-            self.user_events.insert(caller, &user_events);
+    /// Outcome of a community amendment
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum AmendmentStatus {
+        /// Still collecting votes
+        Open,
+        /// Passed and applied as a new revision of the event
+        Applied,
+        /// Voted down
+        Rejected,
+        /// Passed, but the event's text, tags or existing evidence were revised after the
+        /// amendment was proposed
+        Superseded,
+        /// Closed undecided once `pending_timeout` had passed
+        Expired,
+    }
 
-            Ok(event_id)
-        }
+    /// An edit to an event proposed by any account and decided by vote
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Amendment {
+        pub id: u64,
+        pub event_id: u64,
+        pub proposer: AccountId,
+        pub title: String,
+        pub description: String,
+        pub evidence_sources: Vec<Evidence>,
+        pub tags: Vec<String>,
+        /// Whether applying the amendment outdates earlier votes on the event; set when
+        /// it is more than a minor edit of the content it was written against
+        pub substantive: bool,
+        /// Revision of the event the amendment was written against
+        pub base_revision: u32,
+        pub status: AmendmentStatus,
+        /// Total weight of the votes in favour
+        pub support_votes: Balance,
+        /// Total weight of the votes against
+        pub reject_votes: Balance,
+        /// Number of accounts that voted
+        pub voter_count: u32,
+        pub created_at: u64,
+        /// Revision created when the amendment was applied
+        pub applied_revision: Option<u32>,
+        /// Deposit paid by the proposer, held until it is settled after the amendment closes
+        pub deposit: Balance,
+        pub deposit_status: DepositStatus,
+    }
 
-        /// Generates a unique event ID by incrementing the counter
-        fn generate_event_id(&mut self) -> u64 {
-            self.event_count = self.event_count.saturating_add(1);
-            self.event_count
-        }
+    /// A vote on an amendment
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AmendmentVote {
+        pub support: bool,
+        /// Weight counted towards the amendment's support or reject total
+        pub weight: Balance,
+        /// Funds held by the contract until the amendment is closed (token-weighted mode only)
+        pub stake: Balance,
+    }
 
-        /// Checks if a voter has already voted on a specific event
-        /// 
-        /// # Arguments
-        /// * `event_id` - The ID of the event to check
-        /// * `voter` - The account address of the voter
-        /// 
-        /// # Returns
-        /// * `true` if the voter has already voted on this event
-        /// * `false` if the voter has not voted on this event
-        #[ink(message)]
-        pub fn has_voted(&self, event_id: u64, voter: AccountId) -> bool {
-            self.votes.get((event_id, voter)).is_some()
-        }
+    /// Who attached an evidence source after submission, and when
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct EvidenceContribution {
+        /// Position of the source in `evidence_sources` when it was added
+        pub evidence_index: u32,
+        pub contributor: AccountId,
+        pub timestamp: u64,
+        /// Whether the contributor marked the source as changing the picture
+        pub significant: bool,
+        /// Revision created by adding the source
+        pub revision: u32,
+        /// Key of the source's rating tally
+        pub evidence_key: Hash,
+        /// Value transferred with the source, held until it is settled
+        pub deposit: Balance,
+        pub deposit_status: DepositStatus,
+    }
 
-        /// Records a vote on a historical event
-        /// 
-        /// # Arguments
-        /// * `event_id` - The ID of the event to vote on
-        /// * `support` - true for support vote, false for challenge vote
-        /// 
-        /// # Returns
-        /// * `Ok(())` if the vote was recorded successfully
-        /// * `Err(Error::EventNotFound)` if the event doesn't exist
-        /// * `Err(Error::AlreadyVoted)` if the user has already voted on this event
-        #[ink(message)]
-        pub fn vote(&mut self, event_id: u64, support: bool) -> Result<(), Error> {
-            // Validate event exists
-            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+    /// Snapshot of an event's content at one revision
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct EventRevision {
+        /// 0 for the content as submitted, then counting up with each edit
+        pub revision: u32,
+        pub title: String,
+        pub description: String,
+        pub evidence_sources: Vec<Evidence>,
+        pub tags: Vec<String>,
+        pub author: AccountId,
+        pub timestamp: u64,
+        /// Whether the edit changed the substance of the event
+        pub substantive: bool,
+    }
 
-            // Check if user has already voted (prevent duplicates)
-            let caller = self.env().caller();
-            if self.has_voted(event_id, caller) {
-                return Err(Error::AlreadyVoted);
-            }
+    /// Records a single move of an event between timelines
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TimelineTransition {
+        pub from: Timeline,
+        pub to: Timeline,
+        pub block_number: BlockNumber,
+        pub timestamp: u64,
+        pub consensus_score: u8,
+    }
 
-            // Record vote in votes mapping
-            let now = self.env().block_timestamp();
-            let vote = Vote {
-                voter: caller,
-                event_id,
-                support,
-                timestamp: now,
-            };
-            self.votes.insert((event_id, caller), &vote);
+    /// Lifecycle of a submission deposit
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DepositStatus {
+        /// Held by the contract until the review window closes
+        Held,
+        /// Returned to the submitter in full
+        Refunded,
+        /// Partially sent to the treasury, remainder returned to the submitter
+        Slashed,
+    }
 
-            // Update event's support_votes or challenge_votes count
-            if support {
-                event.support_votes = event.support_votes.saturating_add(1);
-            } else {
-                event.challenge_votes = event.challenge_votes.saturating_add(1);
-            }
+    /// Deposit paid when submitting an event
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct SubmissionDeposit {
+        pub amount: Balance,
+        pub status: DepositStatus,
+        /// Portion sent to the treasury when slashed
+        pub slashed: Balance,
+    }
 
-            // Call calculate_consensus_score
-            self.calculate_consensus_score(&mut event);
+    /// Duties the owner can delegate to other accounts
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Role {
+        /// Grants and revokes every role except `Admin`
+        Admin,
+        /// Hides and unhides abusive events
+        Moderator,
+        /// Manages the tags attached to events
+        Curator,
+        /// Attests that an evidence source checks out
+        Verifier,
+    }
 
-            // Call check_timeline_movement
-            self.check_timeline_movement(&mut event);
+    /// A verifier's confirmation of one evidence source
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct EvidenceAttestation {
+        /// Position of the source in the event's current `evidence_sources`
+        pub evidence_index: u32,
+        pub verifier: AccountId,
+        pub timestamp: u64,
+    }
 
-            // Store updated event
-            self.events.insert(event_id, &event);
+    /// Tunable protocol parameters, set at instantiation and changeable by the owner
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Config {
+        /// Minimum time (in milliseconds) between two vote actions by the same account on the same event
+        pub vote_change_cooldown: u64,
+        /// Minimum number of voters before an event can leave the Pending timeline
+        pub min_quorum: u32,
+        /// Consensus score (percent) at or above which an event enters Canonical
+        pub canonical_threshold: u8,
+        /// Consensus score (percent) at or below which an event enters Alternative
+        pub alternative_threshold: u8,
+        /// A Canonical event stays Canonical while its score is at or above this value
+        pub canonical_exit_threshold: u8,
+        /// An Alternative event stays Alternative while its score is at or below this value
+        pub alternative_exit_threshold: u8,
+        /// Credits each account receives per period in `Quadratic` mode
+        pub credits_per_period: u32,
+        /// Length of a credit period in milliseconds
+        pub credit_period: u64,
+        /// Base lock period in milliseconds for conviction votes
+        pub conviction_lock_period: u64,
+        /// Base value that must be transferred with `submit_event`
+        pub submission_deposit: Balance,
+        /// Additional deposit per byte of the SCALE-encoded event
+        pub deposit_per_byte: Balance,
+        /// Time in milliseconds after submission before a deposit can be settled
+        pub review_window: u64,
+        /// Percentage of the deposit sent to the treasury when an event is slashed
+        pub slash_percent: u8,
+        /// Alternative events at or below this consensus score are slashed
+        pub slash_score_threshold: u8,
+        /// Time in milliseconds after submission during which the submitter can make minor edits
+        pub revision_grace_period: u64,
+        /// Share of amendment votes (percent) at or above which an amendment is applied;
+        /// at or below 100 minus this value it is rejected
+        pub amendment_threshold: u8,
+        /// Minimum time in milliseconds between two `add_evidence` calls by the same account
+        pub evidence_cooldown: u64,
+        /// Base value that must be transferred with `add_evidence`
+        pub evidence_deposit: Balance,
+        /// Time in milliseconds after submission after which the deposit of an event that
+        /// never left Pending is refunded in full
+        pub pending_timeout: u64,
+    }
 
-            Ok(())
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                vote_change_cooldown: 0,
+                min_quorum: 3,
+                canonical_threshold: 75,
+                alternative_threshold: 25,
+                canonical_exit_threshold: 70,
+                alternative_exit_threshold: 30,
+                credits_per_period: 100,
+                // One week
+                credit_period: 604_800_000,
+                // One day
+                conviction_lock_period: 86_400_000,
+                submission_deposit: 0,
+                deposit_per_byte: 0,
+                // One week
+                review_window: 604_800_000,
+                slash_percent: 50,
+                slash_score_threshold: 10,
+                // One day
+                revision_grace_period: 86_400_000,
+                amendment_threshold: 66,
+                // One hour
+                evidence_cooldown: 3_600_000,
+                evidence_deposit: 0,
+                // Four weeks
+                pending_timeout: 2_419_200_000,
+            }
         }
+    }
 
-        /// Calculates the consensus score for an event based on votes
-        /// 
-        /// # Arguments
-        /// * `event` - Mutable reference to the event to calculate score for
-        /// 
-        /// Formula: (support_votes * 100) / total_votes
-        fn calculate_consensus_score(&self, event: &mut HistoricalEvent) {
-            let total_votes = event.support_votes.saturating_add(event.challenge_votes);
-            
-            if total_votes == 0 {
-                event.consensus_score = 0;
-            } else {
-                // Calculate percentage using integer arithmetic: (support_votes * 100) / total_votes
-                let numerator = event.support_votes.saturating_mul(100);
-                let score = numerator.checked_div(total_votes).unwrap_or(0);
-                event.consensus_score = score.min(100) as u8;
+    impl Config {
+        /// Checks that the thresholds leave room for a Disputed band and a quorum is required
+        fn validate(&self) -> Result<(), Error> {
+            if self.min_quorum == 0
+                || self.canonical_threshold > 100
+                || self.alternative_threshold >= self.canonical_threshold
+                || self.canonical_exit_threshold > self.canonical_threshold
+                || self.alternative_exit_threshold < self.alternative_threshold
+                || self.alternative_exit_threshold >= self.canonical_exit_threshold
+                || self.credit_period == 0
+                || self.slash_percent > 100
+                || self.slash_score_threshold > self.alternative_threshold
+                || self.amendment_threshold <= 50
+                || self.amendment_threshold > 100
+                || self.pending_timeout < self.review_window
+            {
+                return Err(Error::InvalidConfig);
             }
+            Ok(())
         }
+    }
 
-        /// Checks if an event should move between timelines based on consensus score
-        /// 
-        /// # Arguments
-        /// * `event` - Mutable reference to the event to check
-        /// 
-        /// Timeline movement rules:
-        /// - Score >= 75: Move to Canonical timeline
-        /// - Score <= 25: Move to Alternative timeline
-        /// - Score 26-74: Remain in Disputed timeline
-        fn check_timeline_movement(&mut self, event: &mut HistoricalEvent) {
-            let old_timeline = event.timeline;
-            let new_timeline = if event.consensus_score >= 75 {
-                Timeline::Canonical
-            } else if event.consensus_score <= 25 {
-                Timeline::Alternative
-            } else {
-                Timeline::Disputed
-            };
+    /// A page of historical events returned by the paginated query messages
+    /// 
+    /// Cursors are positions in the underlying index, not event IDs. When
+    /// `check_timeline_movement` moves an event out of a timeline, the events after it
+    /// shift down by one, so a timeline walk in progress can skip an event; restart from
+    /// cursor 0 if `total` changes between pages.
+    #[derive(Debug, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EventPage {
+        pub events: Vec<HistoricalEvent>,
+        /// Cursor to pass to the next call, or `None` if this is the last page
+        pub next_cursor: Option<u64>,
+        /// Total number of events matching the query
+        pub total: u64,
+    }
 
-            // Only update if timeline changed
-            if old_timeline != new_timeline {
-                // Remove from old timeline
-                if let Some(mut old_events) = self.timeline_events.get(old_timeline) {
-                    old_events.retain(|&id| id != event.id);
-                    self.timeline_events.insert(old_timeline, &old_events);
-                }
+    /// A known tag together with the number of events carrying it
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TagInfo {
+        pub tag: String,
+        pub event_count: u32,
+    }
 
-                // Add to new timeline
-                let mut new_events = self.timeline_events.get(new_timeline).unwrap_or_default();
-                new_events.push(event.id);
-                self.timeline_events.insert(new_timeline, &new_events);
+    /// How the tags of an `EventFilter` are combined
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TagMatch {
+        /// Event carries at least one of the tags
+        Any,
+        /// Event carries every one of the tags
+        All,
+    }
 
-                // Update event's timeline
-                event.timeline = new_timeline;
+    /// Criteria for `query_events` and `query_event_ids`; empty or `None` fields match everything
+    /// 
+    /// At least one indexed criterion (tags or a timeline) is required; the date range
+    /// only narrows the events those indexes return.
+    #[derive(Debug, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EventFilter {
+        pub tags: Vec<String>,
+        pub match_mode: TagMatch,
+        pub timeline: Option<Timeline>,
+        /// Earliest historical date (inclusive)
+        pub date_from: Option<u64>,
+        /// Latest historical date (inclusive)
+        pub date_to: Option<u64>,
+    }
+
+    impl EventFilter {
+        /// The inclusive date bounds, or `None` if the filter has no date range
+        fn date_range(&self) -> Option<(u64, u64)> {
+            if self.date_from.is_none() && self.date_to.is_none() {
+                return None;
             }
+            Some((self.date_from.unwrap_or(u64::MIN), self.date_to.unwrap_or(u64::MAX)))
         }
+    }
 
-        /// Retrieves a historical event by its ID
-        /// 
-        /// # Arguments
-        /// * `event_id` - The unique ID of the event to retrieve
-        /// 
-        /// # Returns
-        /// * `Some(HistoricalEvent)` if the event exists
-        /// * `None` if the event doesn't exist
-        #[ink(message)]
-        pub fn get_event(&self, event_id: u64) -> Option<HistoricalEvent> {
-            self.events.get(event_id)
+    /// Maximum number of events returned in a single page
+    pub const MAX_PAGE_SIZE: u32 = 50;
+
+    /// Encoded size budget for the events in one page, in bytes
+    /// 
+    /// Message output goes through a 16 KiB buffer, so pages and exports stop adding
+    /// events once this budget would be exceeded and report where to continue.
+    pub const MAX_PAGE_BYTES: usize = 12 * 1024;
+
+    // Content limits. Storage values are encoded through a 16 KiB buffer, so an event
+    // at every limit (about 9 KB encoded) must still fit in one cell together with the
+    // extra fields of the amendment and revision records that copy its content.
+
+    /// Maximum length of an event title in bytes
+    pub const MAX_TITLE_LENGTH: usize = 200;
+
+    /// Maximum length of an event description in bytes
+    pub const MAX_DESCRIPTION_LENGTH: usize = 4_000;
+
+    /// Maximum length of an evidence citation or archived-copy reference in bytes
+    pub const MAX_EVIDENCE_LENGTH: usize = 200;
+
+    /// Maximum number of evidence sources on one event
+    pub const MAX_EVIDENCE_SOURCES: usize = 10;
+
+    /// Maximum number of tags on one event
+    pub const MAX_TAGS: usize = 5;
+
+    /// Maximum length of a single tag in bytes
+    pub const MAX_TAG_LENGTH: usize = 50;
+
+    /// Maximum number of bytes a minor edit may change in the title or the description
+    pub const MAX_MINOR_EDIT_LENGTH: usize = 20;
+
+    /// Storage layout version written by this code; bump it and add a step to
+    /// `migrate_step` whenever a stored type changes shape
+    /// 
+    /// Version 0 is the original unversioned layout. Version 1 covers every change
+    /// made since: typed evidence, weighted votes and tallies, revisions, moderation
+    /// and evidence quality scores. The version 0 step converts all of them at once.
+    pub const STORAGE_VERSION: u32 = 1;
+
+    /// Account that instantiated the version 0 deployment (`deployment-info.json`)
+    /// 
+    /// Version 0 code had no owner; this account becomes owner and treasury when that
+    /// storage is migrated. Change it before building the upgrade for another deployment.
+    const VERSION_0_OWNER: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6,
+        0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+    ];
+
+    /// Storage key of the version 0 event mapping (the auto key of `events` back then)
+    const LEGACY_EVENTS_KEY: u32 = KeyComposer::from_str("HistoryProtocol::events");
+
+    /// Storage key of the version 0 vote mapping (the auto key of `votes` back then)
+    const LEGACY_VOTES_KEY: u32 = KeyComposer::from_str("HistoryProtocol::votes");
+
+    /// Storage key of the event mapping since version 1
+    const EVENTS_KEY: u32 = KeyComposer::from_str("HistoryProtocol::events::v1");
+
+    /// Storage key of the vote mapping since version 1
+    const VOTES_KEY: u32 = KeyComposer::from_str("HistoryProtocol::votes::v1");
+
+    /// Contract errors
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        EventNotFound,
+        AlreadyVoted,
+        EmptyTitle,
+        EmptyDescription,
+        NoEvidence,
+        EmptyEvidence,
+        NoTags,
+        TooManyTags,
+        EmptyTag,
+        DuplicateTag,
+        NotVoted,
+        VoteCooldown,
+        Unauthorized,
+        InvalidConfig,
+        InsufficientStake,
+        UnexpectedValue,
+        TransferFailed,
+        WrongVotingMode,
+        InvalidVoteCount,
+        InsufficientCredits,
+        InsufficientDeposit,
+        TitleTooLong,
+        DescriptionTooLong,
+        EvidenceTooLong,
+        TagTooLong,
+        NoDeposit,
+        DepositAlreadySettled,
+        ReviewWindowOpen,
+        EventStillPending,
+        EvidenceNotFound,
+        AlreadyAttested,
+        Paused,
+        UpgradeFailed,
+        MigrationPending,
+        UnsupportedStorageVersion,
+        InvalidEventId,
+        EventAlreadyExists,
+        BatchTooLarge,
+        GracePeriodExpired,
+        AmendmentNotFound,
+        AmendmentClosed,
+        InvalidUrl,
+        InvalidIsbn,
+        InvalidDoi,
+        InvalidCid,
+        TooManyEvidence,
+        DuplicateEvidence,
+        EvidenceCooldown,
+        QueryTooBroad,
+        SubstantiveEdit,
+        AmendmentStillOpen,
+    }
+
+    /// Emitted when an account attaches a new evidence source to an existing event
+    #[ink(event)]
+    pub struct EvidenceAdded {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        contributor: AccountId,
+        evidence_index: u32,
+        significant: bool,
+        revision: u32,
+    }
+
+    /// Emitted when an account rates an evidence source
+    #[ink(event)]
+    pub struct EvidenceRated {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        rater: AccountId,
+        evidence_index: u32,
+        rating: EvidenceRating,
+        evidence_quality_score: Option<u8>,
+    }
+
+    /// Emitted when a submission deposit is refunded or slashed
+    #[ink(event)]
+    pub struct DepositSettled {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        submitter: AccountId,
+        refunded: Balance,
+        slashed: Balance,
+    }
+
+    /// Emitted when the deposit for an added evidence source is refunded or slashed
+    #[ink(event)]
+    pub struct EvidenceDepositSettled {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        contributor: AccountId,
+        evidence_index: u32,
+        refunded: Balance,
+        slashed: Balance,
+    }
+
+    /// Emitted when an amendment proposer's deposit is refunded or slashed
+    #[ink(event)]
+    pub struct AmendmentDepositSettled {
+        #[ink(topic)]
+        amendment_id: u64,
+        #[ink(topic)]
+        proposer: AccountId,
+        refunded: Balance,
+        slashed: Balance,
+    }
+
+    /// Emitted when locked stake is returned to its owner
+    #[ink(event)]
+    pub struct StakeUnlocked {
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a new historical event is submitted
+    #[ink(event)]
+    pub struct EventSubmitted {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        submitter: AccountId,
+        timeline: Timeline,
+    }
+
+    /// Emitted when a vote is cast on a historical event
+    #[ink(event)]
+    pub struct VoteCast {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        voter: AccountId,
+        support: bool,
+        weight: Balance,
+        consensus_score: u8,
+    }
+
+    /// Emitted when a voter switches an existing vote between support and challenge
+    #[ink(event)]
+    pub struct VoteChanged {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        voter: AccountId,
+        support: bool,
+        consensus_score: u8,
+    }
+
+    /// Emitted when a voter withdraws their vote
+    #[ink(event)]
+    pub struct VoteRetracted {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        voter: AccountId,
+        consensus_score: u8,
+    }
+
+    /// Emitted when a historical event moves between timelines
+    #[ink(event)]
+    pub struct TimelineChanged {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        old_timeline: Timeline,
+        #[ink(topic)]
+        new_timeline: Timeline,
+        consensus_score: u8,
+    }
+
+    /// Emitted when the owner updates the protocol parameters
+    #[ink(event)]
+    pub struct ConfigChanged {
+        #[ink(topic)]
+        changed_by: AccountId,
+        config: Config,
+    }
+
+    /// Emitted when ownership changes hands or is renounced
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: Option<AccountId>,
+        #[ink(topic)]
+        new_owner: Option<AccountId>,
+    }
+
+    /// Emitted when a role is granted to an account
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        role: Role,
+        granted_by: AccountId,
+    }
+
+    /// Emitted when a role is revoked from an account
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        role: Role,
+        revoked_by: AccountId,
+    }
+
+    /// Emitted when a moderator hides or unhides an event
+    #[ink(event)]
+    pub struct EventVisibilityChanged {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        moderator: AccountId,
+        hidden: bool,
+    }
+
+    /// Emitted when a curator replaces the tags of an event
+    #[ink(event)]
+    pub struct EventTagsChanged {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        curator: AccountId,
+        tags: Vec<String>,
+    }
+
+    /// Emitted when a verifier attests one of an event's evidence sources
+    #[ink(event)]
+    pub struct EvidenceAttested {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        verifier: AccountId,
+        evidence_index: u32,
+    }
+
+    /// Emitted when an admin pauses the contract
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Emitted when an admin lifts the pause
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Emitted when the contract code is replaced
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+        upgraded_by: AccountId,
+    }
+
+    /// Emitted when `migrate` brings storage forward to the current layout
+    #[ink(event)]
+    pub struct StorageMigrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    /// Emitted when an event's content is replaced by a new revision
+    #[ink(event)]
+    pub struct RevisionApplied {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        author: AccountId,
+        revision: u32,
+        substantive: bool,
+    }
+
+    /// Emitted when an amendment to an event is proposed
+    #[ink(event)]
+    pub struct AmendmentProposed {
+        #[ink(topic)]
+        amendment_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        proposer: AccountId,
+    }
+
+    /// Emitted when an amendment is decided by vote or expires
+    #[ink(event)]
+    pub struct AmendmentResolved {
+        #[ink(topic)]
+        amendment_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        status: AmendmentStatus,
+        applied_revision: Option<u32>,
+    }
+
+    /// The main contract storage
+    /// 
+    /// Only `event_count` lives in the root cell, as it did in version 0; every other
+    /// value sits behind a `Mapping` or `Lazy` so older roots still decode. Mappings
+    /// whose values changed shape moved to new keys, and the version 0 data stays
+    /// readable through the `legacy_*` mappings until `migrate` has converted it.
+    #[ink(storage)]
+    pub struct HistoryProtocol {
+        events: Mapping<u64, HistoricalEvent, ManualKey<EVENTS_KEY>>,
+        event_count: u64,
+        votes: Mapping<(u64, AccountId), Vote, ManualKey<VOTES_KEY>>,
+        timeline_events: Mapping<Timeline, Vec<u64>>,
+        user_events: Mapping<AccountId, Vec<u64>>,
+        tag_events: Mapping<(String, u32), u64>,
+        tag_event_count: Mapping<String, u32>,
+        tag_event_index: Mapping<(String, u64), u32>,
+        known_tags: Mapping<u32, String>,
+        known_tag_count: Lazy<u32>,
+        config: Lazy<Config>,
+        last_vote_action: Mapping<(u64, AccountId), u64>,
+        owner: Lazy<Option<AccountId>>,
+        timeline_history: Mapping<(u64, u32), TimelineTransition>,
+        timeline_history_count: Mapping<u64, u32>,
+        voting_mode: Lazy<VotingMode>,
+        vote_credits: Mapping<AccountId, VoteCredits>,
+        credit_spends: Mapping<(AccountId, u32), CreditSpend>,
+        credit_spend_count: Mapping<AccountId, u32>,
+        stake_locks: Mapping<AccountId, Vec<StakeLock>>,
+        deposits: Mapping<u64, SubmissionDeposit>,
+        treasury: Lazy<AccountId>,
+        pending_owner: Lazy<Option<AccountId>>,
+        roles: Mapping<(AccountId, Role), ()>,
+        evidence_attestations: Mapping<Hash, Vec<EvidenceAttestation>>,
+        paused: Lazy<bool>,
+        storage_version: Lazy<u32>,
+        event_voters: Mapping<(u64, u32), AccountId>,
+        event_voter_count: Mapping<u64, u32>,
+        event_voter_index: Mapping<(u64, AccountId), u32>,
+        event_revisions: Mapping<(u64, u32), EventRevision>,
+        amendments: Mapping<u64, Amendment>,
+        amendment_count: Lazy<u64>,
+        event_amendments: Mapping<(u64, u32), u64>,
+        event_amendment_count: Mapping<u64, u32>,
+        amendment_votes: Mapping<(u64, AccountId), AmendmentVote>,
+        evidence_tallies: Mapping<Hash, EvidenceTally>,
+        evidence_ratings: Mapping<(Hash, AccountId), WeightedRating>,
+        evidence_contributions: Mapping<u64, Vec<EvidenceContribution>>,
+        last_evidence_added: Mapping<AccountId, u64>,
+        legacy_events: Mapping<u64, LegacyEvent, ManualKey<LEGACY_EVENTS_KEY>>,
+        legacy_votes: Mapping<(u64, AccountId), LegacyVote, ManualKey<LEGACY_VOTES_KEY>>,
+        migration_cursor: Lazy<u64>,
+        legacy_votes_remaining: Lazy<u64>,
+        legacy_vote_tallies: Mapping<u64, LegacyVoteTally>,
+    }
+
+    impl HistoryProtocol {
+        /// Constructor that initializes the contract
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::init(Config::default(), VotingMode::OneAccountOneVote)
         }
 
-        /// Retrieves all events in a specific timeline
-        /// 
-        /// # Arguments
-        /// * `timeline` - The timeline to retrieve events from (Canonical, Disputed, or Alternative)
+        /// Constructor that initializes the contract with custom protocol parameters
         /// 
         /// # Returns
-        /// * `Vec<HistoricalEvent>` - Vector of all events in the specified timeline
-        #[ink(message)]
-        pub fn get_events_by_timeline(&self, timeline: Timeline) -> Vec<HistoricalEvent> {
-            // Retrieve event IDs from timeline_events mapping
-            let event_ids = self.timeline_events.get(timeline).unwrap_or_default();
-            
-            // Fetch full event data for each ID
-            let mut events = Vec::new();
-            for event_id in event_ids.iter() {
-                if let Some(event) = self.events.get(*event_id) {
-                    events.push(event);
-                }
-            }
-            
-            events
+        /// * `Err(Error::InvalidConfig)` - If the thresholds or quorum are inconsistent
+        #[ink(constructor)]
+        pub fn with_config(config: Config) -> Result<Self, Error> {
+            Self::with_voting_mode(config, VotingMode::OneAccountOneVote)
         }
 
-        /// Retrieves all events submitted by a specific user
+        /// Constructor that initializes the contract with custom protocol parameters and voting mode
         /// 
-        /// # Arguments
-        /// * `user` - The account address of the user
+        /// The voting mode cannot be changed after instantiation.
         /// 
         /// # Returns
-        /// * `Vec<HistoricalEvent>` - Vector of all events submitted by the user
-        #[ink(message)]
-        pub fn get_user_events(&self, user: AccountId) -> Vec<HistoricalEvent> {
-            // Retrieve event IDs from user_events mapping
-            let event_ids = self.user_events.get(user).unwrap_or_default();
-            
-            // Fetch full event data for each ID
-            let mut events = Vec::new();
-            for event_id in event_ids.iter() {
-                if let Some(event) = self.events.get(*event_id) {
-                    events.push(event);
-                }
-            }
-            
-            events
+        /// * `Err(Error::InvalidConfig)` - If the thresholds or quorum are inconsistent
+        #[ink(constructor)]
+        pub fn with_voting_mode(config: Config, voting_mode: VotingMode) -> Result<Self, Error> {
+            config.validate()?;
+            Ok(Self::init(config, voting_mode))
         }
 
-        /// Retrieves all events that contain a specific tag
+        /// Builds the initial storage; the instantiating account becomes the owner and treasury
+        fn init(config: Config, voting_mode: VotingMode) -> Self {
+            let caller = Self::env().caller();
+            let mut contract = Self {
+                events: Mapping::default(),
+                event_count: 0,
+                votes: Mapping::default(),
+                timeline_events: Mapping::default(),
+                user_events: Mapping::default(),
+                tag_events: Mapping::default(),
+                tag_event_count: Mapping::default(),
+                tag_event_index: Mapping::default(),
+                known_tags: Mapping::default(),
+                known_tag_count: Lazy::new(),
+                config: Lazy::new(),
+                last_vote_action: Mapping::default(),
+                owner: Lazy::new(),
+                timeline_history: Mapping::default(),
+                timeline_history_count: Mapping::default(),
+                voting_mode: Lazy::new(),
+                vote_credits: Mapping::default(),
+                credit_spends: Mapping::default(),
+                credit_spend_count: Mapping::default(),
+                stake_locks: Mapping::default(),
+                deposits: Mapping::default(),
+                treasury: Lazy::new(),
+                pending_owner: Lazy::new(),
+                roles: Mapping::default(),
+                evidence_attestations: Mapping::default(),
+                paused: Lazy::new(),
+                storage_version: Lazy::new(),
+                event_voters: Mapping::default(),
+                event_voter_count: Mapping::default(),
+                event_voter_index: Mapping::default(),
+                event_revisions: Mapping::default(),
+                amendments: Mapping::default(),
+                amendment_count: Lazy::new(),
+                event_amendments: Mapping::default(),
+                event_amendment_count: Mapping::default(),
+                amendment_votes: Mapping::default(),
+                evidence_tallies: Mapping::default(),
+                evidence_ratings: Mapping::default(),
+                evidence_contributions: Mapping::default(),
+                last_evidence_added: Mapping::default(),
+                legacy_events: Mapping::default(),
+                legacy_votes: Mapping::default(),
+                migration_cursor: Lazy::new(),
+                legacy_votes_remaining: Lazy::new(),
+                legacy_vote_tallies: Mapping::default(),
+            };
+            contract.config.set(&config);
+            contract.owner.set(&Some(caller));
+            contract.voting_mode.set(&voting_mode);
+            contract.treasury.set(&caller);
+            contract.storage_version.set(&STORAGE_VERSION);
+            contract
+        }
+
+        /// Returns the stored protocol parameters
+        fn config(&self) -> Config {
+            self.config.get().unwrap_or_default()
+        }
+
+        /// Submits a new historical event to the protocol
         /// 
         /// # Arguments
-        /// * `tag` - The tag to filter events by
+        /// * `title` - The title of the historical event
+        /// * `date` - Unix timestamp of when the event occurred
+        /// * `description` - Detailed description of the event
+        /// * `evidence_sources` - Vector of typed evidence sources (minimum 1)
+        /// * `tags` - Vector of category tags (minimum 1, maximum 5)
+        /// 
+        /// The transferred value must cover the deposit returned by `estimate_deposit`,
+        /// which grows with the encoded size of the event; it is held until the event
+        /// is reviewed and then settled through `settle_deposit`.
         /// 
         /// # Returns
-        /// * `Vec<HistoricalEvent>` - Vector of all events containing the specified tag
+        /// * `Ok(u64)` - The unique event ID if successful
+        /// * `Err(Error::EmptyTitle)` - If the title is empty
+        /// * `Err(Error::EmptyDescription)` - If the description is empty
+        /// * `Err(Error::NoEvidence)` - If no evidence sources are given
+        /// * `Err(Error::TooManyEvidence)` - If more than `MAX_EVIDENCE_SOURCES` sources are given
+        /// * `Err(Error::DuplicateEvidence)` - If the same source is given twice
+        /// * `Err(Error::EmptyEvidence)` - If an evidence citation or archived-copy reference is empty
+        /// * `Err(Error::NoTags)` - If no tags are given
+        /// * `Err(Error::TooManyTags)` - If more than `MAX_TAGS` tags are given
+        /// * `Err(Error::EmptyTag)` - If a tag is empty
+        /// * `Err(Error::DuplicateTag)` - If the same tag is given twice
+        /// * `Err(Error::TitleTooLong)` - If the title exceeds `MAX_TITLE_LENGTH`
+        /// * `Err(Error::DescriptionTooLong)` - If the description exceeds `MAX_DESCRIPTION_LENGTH`
+        /// * `Err(Error::EvidenceTooLong)` - If an evidence citation or archived copy exceeds `MAX_EVIDENCE_LENGTH`
+        /// * `Err(Error::InvalidUrl)`, `InvalidIsbn`, `InvalidDoi`, `InvalidCid` - If a citation doesn't match its kind
+        /// * `Err(Error::TagTooLong)` - If a tag exceeds `MAX_TAG_LENGTH`
+        /// * `Err(Error::InsufficientDeposit)` - If the transferred value is below the deposit
+        #[ink(message, payable)]
+        pub fn submit_event(
+            &mut self,
+            title: String,
+            date: u64,
+            description: String,
+            evidence_sources: Vec<Evidence>,
+            tags: Vec<String>,
+        ) -> Result<u64, Error> {
+            self.ensure_writable()?;
+            Self::validate_event_content(&title, &description, &evidence_sources, &tags)?;
+
+            // Get current timestamp and caller
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+
+            // Create HistoricalEvent with initial Pending timeline and 0 consensus score
+            let mut event = HistoricalEvent {
+                id: 0,
+                title,
+                date,
+                description,
+                evidence_sources,
+                tags,
+                submitter: caller,
+                timeline: Timeline::Pending,
+                consensus_score: 0,
+                support_votes: 0,
+                challenge_votes: 0,
+                voter_count: 0,
+                created_at: now,
+                hidden: false,
+                revision: 0,
+                substantive_revision: 0,
+                evidence_quality_score: None,
+            };
+
+            // The deposit scales with the storage the event will occupy
+            let deposit = self.env().transferred_value();
+            if deposit < self.required_deposit(&event) {
+                return Err(Error::InsufficientDeposit);
+            }
+
+            // Generate unique event ID
+            let event_id = self.generate_event_id();
+            event.id = event_id;
+
+            // Store event in events mapping
+            self.events.insert(event_id, &event);
+
+            // Add it to the Pending timeline, the submitter's list and the tag index
+            self.index_event(&event);
+
+            // Hold the deposit until the event has been reviewed
+            if deposit > 0 {
+                self.deposits.insert(event_id, &SubmissionDeposit {
+                    amount: deposit,
+                    status: DepositStatus::Held,
+                    slashed: 0,
+                });
+            }
+
+            self.env().emit_event(EventSubmitted {
+                event_id,
+                submitter: caller,
+                timeline: Timeline::Pending,
+            });
+
+            Ok(event_id)
+        }
+
+        /// Returns the deposit `submit_event` would require for the given fields
+        /// 
+        /// # Arguments
+        /// * `title` - The title of the historical event
+        /// * `description` - Detailed description of the event
+        /// * `evidence_sources` - Vector of typed evidence sources (minimum 1)
+        /// * `tags` - Vector of category tags
+        /// 
+        /// # Returns
+        /// * `Balance` - `submission_deposit` plus `deposit_per_byte` for each byte of the encoded event
         #[ink(message)]
-        pub fn get_events_by_tag(&self, tag: String) -> Vec<HistoricalEvent> {
-            let mut matching_events = Vec::new();
-            
-            // Iterate through all events
-            for event_id in 1..=self.event_count {
-                if let Some(event) = self.events.get(event_id) {
-                    // Check if event contains the specified tag
-                    if event.tags.contains(&tag) {
-                        matching_events.push(event);
+        pub fn estimate_deposit(
+            &self,
+            title: String,
+            description: String,
+            evidence_sources: Vec<Evidence>,
+            tags: Vec<String>,
+        ) -> Balance {
+            let event = HistoricalEvent {
+                id: 0,
+                title,
+                date: 0,
+                description,
+                evidence_sources,
+                tags,
+                submitter: self.env().caller(),
+                timeline: Timeline::Pending,
+                consensus_score: 0,
+                support_votes: 0,
+                challenge_votes: 0,
+                voter_count: 0,
+                created_at: 0,
+                hidden: false,
+                revision: 0,
+                substantive_revision: 0,
+                evidence_quality_score: None,
+            };
+            self.required_deposit(&event)
+        }
+
+        /// Computes the deposit for storing an event
+        fn required_deposit(&self, event: &HistoricalEvent) -> Balance {
+            let size = scale::Encode::encoded_size(event) as Balance;
+            let config = self.config();
+            config
+                .submission_deposit
+                .saturating_add(config.deposit_per_byte.saturating_mul(size))
+        }
+
+        /// Applies a minor edit to an event (submitter only)
+        /// 
+        /// Only corrections such as typo fixes are applied directly, and only within
+        /// `revision_grace_period` of submission: the evidence and tags must stay the same
+        /// and the title and description may each change by at most `MAX_MINOR_EDIT_LENGTH`
+        /// bytes. Anything larger has to go through `propose_amendment`. The edit is stored
+        /// as a new revision, numbered from 1, and costs `deposit_per_byte` for each byte of
+        /// the stored snapshot; this is added to the event's held deposit.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event to revise
+        /// * `title`, `description`, `evidence_sources`, `tags` - The complete new content
+        /// 
+        /// # Returns
+        /// * `Ok(u32)` - The new revision number
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::Unauthorized)` if the caller is not the submitter
+        /// * `Err(Error::GracePeriodExpired)` if the grace period has passed
+        /// * `Err(Error::SubstantiveEdit)` if the edit is more than a minor correction
+        /// * `Err(Error::InsufficientDeposit)` if the transferred value doesn't cover the snapshot
+        /// * Any content validation error `submit_event` would return
+        #[ink(message, payable)]
+        pub fn propose_revision(
+            &mut self,
+            event_id: u64,
+            title: String,
+            description: String,
+            evidence_sources: Vec<Evidence>,
+            tags: Vec<String>,
+        ) -> Result<u32, Error> {
+            self.ensure_writable()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let caller = self.env().caller();
+            if caller != event.submitter {
+                return Err(Error::Unauthorized);
+            }
+
+            let now = self.env().block_timestamp();
+            let grace_end = event.created_at.saturating_add(self.config().revision_grace_period);
+            if now > grace_end {
+                return Err(Error::GracePeriodExpired);
+            }
+
+            Self::validate_event_content(&title, &description, &evidence_sources, &tags)?;
+            if !Self::is_minor_edit(&event, &title, &description, &evidence_sources, &tags) {
+                return Err(Error::SubstantiveEdit);
+            }
+
+            let revision = EventRevision {
+                revision: 0,
+                title,
+                description,
+                evidence_sources,
+                tags,
+                author: caller,
+                timestamp: now,
+                substantive: false,
+            };
+            let deposit = self.env().transferred_value();
+            if deposit < self.revision_deposit(&event, &revision) {
+                return Err(Error::InsufficientDeposit);
+            }
+            self.hold_deposit(event_id, deposit)?;
+
+            Ok(self.apply_revision(&mut event, revision))
+        }
+
+        /// Checks whether new content only corrects details of an event's current content
+        fn is_minor_edit(
+            event: &HistoricalEvent,
+            title: &str,
+            description: &str,
+            evidence_sources: &[Evidence],
+            tags: &[String],
+        ) -> bool {
+            evidence_sources == event.evidence_sources.as_slice()
+                && tags == event.tags.as_slice()
+                && Self::changed_span(&event.title, title) <= MAX_MINOR_EDIT_LENGTH
+                && Self::changed_span(&event.description, description) <= MAX_MINOR_EDIT_LENGTH
+        }
+
+        /// Returns how many bytes differ between two strings once their common prefix
+        /// and suffix are set aside
+        fn changed_span(old: &str, new: &str) -> usize {
+            let (old, new) = (old.as_bytes(), new.as_bytes());
+            let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+            let suffix = old[prefix..]
+                .iter()
+                .rev()
+                .zip(new[prefix..].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            old.len().max(new.len()).saturating_sub(prefix).saturating_sub(suffix)
+        }
+
+        /// Computes the deposit for the snapshots `apply_revision` will store
+        fn revision_deposit(&self, event: &HistoricalEvent, revision: &EventRevision) -> Balance {
+            let mut size = scale::Encode::encoded_size(revision);
+            if event.revision == 0 {
+                size = size.saturating_add(scale::Encode::encoded_size(&Self::original_revision(event)));
+            }
+            self.config().deposit_per_byte.saturating_mul(size as Balance)
+        }
+
+        /// Adds value to an event's held deposit, creating one if none was paid
+        /// 
+        /// Once the deposit has been settled there is nothing left to refund it with, so
+        /// the value goes to the treasury instead.
+        fn hold_deposit(&mut self, event_id: u64, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Ok(());
+            }
+            match self.deposits.get(event_id) {
+                Some(mut deposit) if deposit.status == DepositStatus::Held => {
+                    deposit.amount = deposit.amount.saturating_add(amount);
+                    self.deposits.insert(event_id, &deposit);
+                }
+                Some(_) => {
+                    self.env()
+                        .transfer(self.get_treasury(), amount)
+                        .map_err(|_| Error::TransferFailed)?;
+                }
+                None => {
+                    self.deposits.insert(event_id, &SubmissionDeposit {
+                        amount,
+                        status: DepositStatus::Held,
+                        slashed: 0,
+                    });
+                }
+            }
+            Ok(())
+        }
+
+        /// Replaces an event's content with a revision, keeping the tag index and
+        /// revision history in step; returns the assigned revision number
+        /// 
+        /// Each revision is stored in its own cell. The submitted content is only
+        /// snapshotted once it is about to change.
+        fn apply_revision(&mut self, event: &mut HistoricalEvent, mut revision: EventRevision) -> u32 {
+            if event.revision == 0 {
+                self.event_revisions.insert((event.id, 0), &Self::original_revision(event));
+            }
+
+            revision.revision = event.revision.saturating_add(1);
+            if revision.tags != event.tags && !event.hidden {
+                self.unindex_event_tags(event.id, &event.tags);
+                self.index_event_tags(event.id, &revision.tags);
+            }
+
+            event.title = revision.title.clone();
+            event.description = revision.description.clone();
+            event.evidence_sources = revision.evidence_sources.clone();
+            event.tags = revision.tags.clone();
+            event.revision = revision.revision;
+            if revision.substantive {
+                event.substantive_revision = revision.revision;
+            }
+            self.update_evidence_quality(event);
+            self.events.insert(event.id, event);
+
+            self.env().emit_event(RevisionApplied {
+                event_id: event.id,
+                author: revision.author,
+                revision: revision.revision,
+                substantive: revision.substantive,
+            });
+
+            self.event_revisions.insert((event.id, revision.revision), &revision);
+            revision.revision
+        }
+
+        /// Builds the revision-0 snapshot of an event from its current content
+        fn original_revision(event: &HistoricalEvent) -> EventRevision {
+            EventRevision {
+                revision: 0,
+                title: event.title.clone(),
+                description: event.description.clone(),
+                evidence_sources: event.evidence_sources.clone(),
+                tags: event.tags.clone(),
+                author: event.submitter,
+                timestamp: event.created_at,
+                substantive: true,
+            }
+        }
+
+        /// Proposes new content for an event, to be decided by vote
+        /// 
+        /// This is how any edit beyond a minor correction is made, by the submitter or
+        /// anyone else; the submitter can apply minor edits directly with `propose_revision`
+        /// during the grace period. An amendment that is more than a minor edit is
+        /// substantive and outdates earlier votes once applied.
+        /// 
+        /// The proposal requires `submission_deposit` plus `deposit_per_byte` for each byte
+        /// of the stored amendment, settled with `settle_amendment_deposit` once the
+        /// amendment is closed.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event to amend
+        /// * `title`, `description`, `evidence_sources`, `tags` - The complete new content
+        /// 
+        /// # Returns
+        /// * `Ok(u64)` - The amendment ID
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::InsufficientDeposit)` if the transferred value is below the deposit
+        /// * Any content validation error `submit_event` would return
+        #[ink(message, payable)]
+        pub fn propose_amendment(
+            &mut self,
+            event_id: u64,
+            title: String,
+            description: String,
+            evidence_sources: Vec<Evidence>,
+            tags: Vec<String>,
+        ) -> Result<u64, Error> {
+            self.ensure_writable()?;
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            Self::validate_event_content(&title, &description, &evidence_sources, &tags)?;
+            let substantive = !Self::is_minor_edit(&event, &title, &description, &evidence_sources, &tags);
+
+            let proposer = self.env().caller();
+            let mut amendment = Amendment {
+                id: 0,
+                event_id,
+                proposer,
+                title,
+                description,
+                evidence_sources,
+                tags,
+                substantive,
+                base_revision: event.revision,
+                status: AmendmentStatus::Open,
+                support_votes: 0,
+                reject_votes: 0,
+                voter_count: 0,
+                created_at: self.env().block_timestamp(),
+                applied_revision: None,
+                deposit: 0,
+                deposit_status: DepositStatus::Held,
+            };
+
+            let deposit = self.env().transferred_value();
+            if deposit < self.amendment_deposit(&amendment) {
+                return Err(Error::InsufficientDeposit);
+            }
+
+            let amendment_id = self.amendment_count.get_or_default().saturating_add(1);
+            self.amendment_count.set(&amendment_id);
+            amendment.id = amendment_id;
+            amendment.deposit = deposit;
+            self.amendments.insert(amendment_id, &amendment);
+
+            let index = self.event_amendment_count.get(event_id).unwrap_or(0);
+            self.event_amendments.insert((event_id, index), &amendment_id);
+            self.event_amendment_count.insert(event_id, &index.saturating_add(1));
+
+            self.env().emit_event(AmendmentProposed {
+                amendment_id,
+                event_id,
+                proposer,
+            });
+
+            Ok(amendment_id)
+        }
+
+        /// Computes the deposit for storing an amendment
+        fn amendment_deposit(&self, amendment: &Amendment) -> Balance {
+            let size = scale::Encode::encoded_size(amendment) as Balance;
+            let config = self.config();
+            config
+                .submission_deposit
+                .saturating_add(config.deposit_per_byte.saturating_mul(size))
+        }
+
+        /// Votes for or against an open amendment
+        /// 
+        /// Votes are weighted as event votes are with `vote`: one per account, the
+        /// transferred stake in `TokenWeighted` mode, or one credit in `Quadratic` mode
+        /// (recorded as a spend on the amended event). A stake is held until the amendment
+        /// is closed and then returned through `reclaim_amendment_stake`.
+        /// 
+        /// Once `min_quorum` accounts have voted, the amendment is applied when the share
+        /// of the weight in favour reaches `amendment_threshold` and rejected when it falls
+        /// to 100 minus that value.
+        /// 
+        /// # Returns
+        /// * `Err(Error::AmendmentNotFound)` if the amendment doesn't exist
+        /// * `Err(Error::AmendmentClosed)` if the amendment was already decided
+        /// * `Err(Error::AlreadyVoted)` if the caller already voted on it
+        /// * `Err(Error::InsufficientStake)` if no value was transferred in `TokenWeighted` mode
+        /// * `Err(Error::UnexpectedValue)` if value was transferred outside `TokenWeighted` mode
+        /// * `Err(Error::InsufficientCredits)` if the caller has no credits left in `Quadratic` mode
+        /// * `Err(Error::TransferFailed)` if the decision's deposit payout could not be transferred
+        #[ink(message, payable)]
+        pub fn vote_amendment(&mut self, amendment_id: u64, support: bool) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut amendment = self.amendments.get(amendment_id).ok_or(Error::AmendmentNotFound)?;
+            if amendment.status != AmendmentStatus::Open {
+                return Err(Error::AmendmentClosed);
+            }
+            let caller = self.env().caller();
+            if self.amendment_votes.contains((amendment_id, caller)) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            let value = self.env().transferred_value();
+            let stake = if self.get_voting_mode() == VotingMode::TokenWeighted {
+                if value == 0 {
+                    return Err(Error::InsufficientStake);
+                }
+                value
+            } else if value != 0 {
+                return Err(Error::UnexpectedValue);
+            } else {
+                0
+            };
+            if self.get_voting_mode() == VotingMode::Quadratic {
+                let now = self.env().block_timestamp();
+                self.spend_vote_credits(caller, amendment.event_id, Some(amendment_id), support, 1, now)?;
+            }
+            // A token-weighted vote counts like an event vote without conviction
+            let weight = if stake > 0 {
+                stake.saturating_mul(Conviction::None.multiplier())
+            } else {
+                1
+            };
+
+            self.amendment_votes.insert((amendment_id, caller), &AmendmentVote {
+                support,
+                weight,
+                stake,
+            });
+            if support {
+                amendment.support_votes = amendment.support_votes.saturating_add(weight);
+            } else {
+                amendment.reject_votes = amendment.reject_votes.saturating_add(weight);
+            }
+            amendment.voter_count = amendment.voter_count.saturating_add(1);
+
+            self.resolve_amendment(&mut amendment);
+            self.amendments.insert(amendment_id, &amendment);
+            Ok(())
+        }
+
+        /// Applies or rejects an amendment once it has enough votes
+        fn resolve_amendment(&mut self, amendment: &mut Amendment) {
+            if amendment.voter_count < self.config().min_quorum {
+                return;
+            }
+            let total = amendment.support_votes.saturating_add(amendment.reject_votes);
+            let score = amendment.support_votes.saturating_mul(100).checked_div(total).unwrap_or(0);
+            let threshold = Balance::from(self.config().amendment_threshold);
+
+            if score >= threshold {
+                let rebased = self.events.get(amendment.event_id).and_then(|event| {
+                    let (evidence_sources, tags) = self.rebase_amendment(&event, amendment)?;
+                    Some((event, evidence_sources, tags))
+                });
+                match rebased {
+                    Some((mut event, evidence_sources, tags)) => {
+                        let revision = self.apply_revision(&mut event, EventRevision {
+                            revision: 0,
+                            title: amendment.title.clone(),
+                            description: amendment.description.clone(),
+                            evidence_sources,
+                            tags,
+                            author: amendment.proposer,
+                            timestamp: self.env().block_timestamp(),
+                            substantive: amendment.substantive,
+                        });
+                        amendment.status = AmendmentStatus::Applied;
+                        amendment.applied_revision = Some(revision);
                     }
+                    None => amendment.status = AmendmentStatus::Superseded,
                 }
+            } else if score <= 100u128.saturating_sub(threshold) {
+                amendment.status = AmendmentStatus::Rejected;
+            } else {
+                return;
             }
-            
-            matching_events
+
+            self.close_amendment(amendment);
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        /// Returns the evidence and tags an amendment should be applied with, or `None` if
+        /// the event changed in a way that conflicts with it
+        /// 
+        /// Sources appended with `add_evidence` since the amendment was proposed are kept
+        /// after the amendment's own, and tags set by a curator are kept if the amendment
+        /// leaves the tags alone; any other change to the event supersedes it.
+        fn rebase_amendment(&self, event: &HistoricalEvent, amendment: &Amendment) -> Option<(Vec<Evidence>, Vec<String>)> {
+            if event.revision == amendment.base_revision {
+                return Some((amendment.evidence_sources.clone(), amendment.tags.clone()));
+            }
+            let base = self.revision_snapshot(event, amendment.base_revision)?;
+            let retagged = event.tags != base.tags;
+            if event.title != base.title
+                || event.description != base.description
+                || (retagged && amendment.tags != base.tags)
+                || !event.evidence_sources.starts_with(&base.evidence_sources)
+            {
+                return None;
+            }
+            let tags = if retagged { event.tags.clone() } else { amendment.tags.clone() };
+
+            let mut evidence_sources = amendment.evidence_sources.clone();
+            for source in event.evidence_sources.iter().skip(base.evidence_sources.len()) {
+                if !evidence_sources.contains(source) {
+                    evidence_sources.push(source.clone());
+                }
+            }
+            (evidence_sources.len() <= MAX_EVIDENCE_SOURCES).then_some((evidence_sources, tags))
+        }
+
+        /// Announces an amendment's final status
+        fn close_amendment(&self, amendment: &Amendment) {
+            self.env().emit_event(AmendmentResolved {
+                amendment_id: amendment.id,
+                event_id: amendment.event_id,
+                status: amendment.status,
+                applied_revision: amendment.applied_revision,
+            });
+        }
+
+        /// Settles the proposer's deposit on a closed amendment
+        /// 
+        /// Callable by anyone. A rejected amendment has `slash_percent` of the deposit sent
+        /// to the treasury; an applied, superseded or expired one is refunded in full.
+        /// 
+        /// # Returns
+        /// * `Err(Error::AmendmentNotFound)` if the amendment doesn't exist
+        /// * `Err(Error::AmendmentStillOpen)` if the amendment has not been closed yet
+        /// * `Err(Error::NoDeposit)` if no deposit was paid for it
+        /// * `Err(Error::DepositAlreadySettled)` if the deposit was already settled
+        /// * `Err(Error::TransferFailed)` if a payout could not be transferred
+        #[ink(message)]
+        pub fn settle_amendment_deposit(&mut self, amendment_id: u64) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut amendment = self.amendments.get(amendment_id).ok_or(Error::AmendmentNotFound)?;
+            if amendment.status == AmendmentStatus::Open {
+                return Err(Error::AmendmentStillOpen);
+            }
+            if amendment.deposit == 0 {
+                return Err(Error::NoDeposit);
+            }
+            if amendment.deposit_status != DepositStatus::Held {
+                return Err(Error::DepositAlreadySettled);
+            }
+
+            let slashed = if amendment.status == AmendmentStatus::Rejected {
+                amendment.deposit.saturating_mul(Balance::from(self.config().slash_percent)) / 100
+            } else {
+                0
+            };
+            let refunded = amendment.deposit.saturating_sub(slashed);
+            amendment.deposit_status = if slashed > 0 {
+                DepositStatus::Slashed
+            } else {
+                DepositStatus::Refunded
+            };
+            self.amendments.insert(amendment_id, &amendment);
+
+            self.env().emit_event(AmendmentDepositSettled {
+                amendment_id,
+                proposer: amendment.proposer,
+                refunded,
+                slashed,
+            });
+
+            if slashed > 0 {
+                self.env()
+                    .transfer(self.get_treasury(), slashed)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            if refunded > 0 {
+                self.env()
+                    .transfer(amendment.proposer, refunded)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            Ok(())
+        }
+
+        /// Closes an amendment that is still undecided after `pending_timeout`
+        /// 
+        /// Callable by anyone. The proposer's deposit can then be refunded in full with
+        /// `settle_amendment_deposit` and voters can reclaim their stakes.
+        /// 
+        /// # Returns
+        /// * `Err(Error::AmendmentNotFound)` if the amendment doesn't exist
+        /// * `Err(Error::AmendmentClosed)` if the amendment was already decided
+        /// * `Err(Error::AmendmentStillOpen)` if `pending_timeout` has not passed since it was proposed
+        #[ink(message)]
+        pub fn expire_amendment(&mut self, amendment_id: u64) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut amendment = self.amendments.get(amendment_id).ok_or(Error::AmendmentNotFound)?;
+            if amendment.status != AmendmentStatus::Open {
+                return Err(Error::AmendmentClosed);
+            }
+            let now = self.env().block_timestamp();
+            if now < amendment.created_at.saturating_add(self.config().pending_timeout) {
+                return Err(Error::AmendmentStillOpen);
+            }
+
+            amendment.status = AmendmentStatus::Expired;
+            self.amendments.insert(amendment_id, &amendment);
+            self.close_amendment(&amendment);
+            Ok(())
+        }
+
+        /// Returns the caller's stake on a closed amendment
+        /// 
+        /// # Returns
+        /// * `Ok(Balance)` - The amount returned (0 if the vote carried no stake or it was reclaimed)
+        /// * `Err(Error::AmendmentNotFound)` if the amendment doesn't exist
+        /// * `Err(Error::AmendmentStillOpen)` if the amendment has not been closed yet
+        /// * `Err(Error::NotVoted)` if the caller did not vote on the amendment
+        /// * `Err(Error::TransferFailed)` if the stake could not be transferred
+        #[ink(message)]
+        pub fn reclaim_amendment_stake(&mut self, amendment_id: u64) -> Result<Balance, Error> {
+            self.ensure_writable()?;
+            let amendment = self.amendments.get(amendment_id).ok_or(Error::AmendmentNotFound)?;
+            if amendment.status == AmendmentStatus::Open {
+                return Err(Error::AmendmentStillOpen);
+            }
+            let caller = self.env().caller();
+            let mut vote = self.amendment_votes.get((amendment_id, caller)).ok_or(Error::NotVoted)?;
+            let amount = vote.stake;
+            if amount == 0 {
+                return Ok(0);
+            }
+
+            vote.stake = 0;
+            self.amendment_votes.insert((amendment_id, caller), &vote);
+
+            self.env().emit_event(StakeUnlocked {
+                account: caller,
+                amount,
+            });
+
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Ok(amount)
+        }
+
+        /// Checks the content fields shared by submissions and revisions
+        fn validate_event_content(
+            title: &str,
+            description: &str,
+            evidence_sources: &[Evidence],
+            tags: &[String],
+        ) -> Result<(), Error> {
+            // Validate required fields
+            if title.is_empty() {
+                return Err(Error::EmptyTitle);
+            }
+            if description.is_empty() {
+                return Err(Error::EmptyDescription);
+            }
+            if evidence_sources.is_empty() {
+                return Err(Error::NoEvidence);
+            }
+            if evidence_sources.len() > MAX_EVIDENCE_SOURCES {
+                return Err(Error::TooManyEvidence);
+            }
+            for source in evidence_sources.iter() {
+                Self::validate_evidence(source)?;
+            }
+            if evidence_sources
+                .iter()
+                .enumerate()
+                .any(|(i, source)| evidence_sources[..i].contains(source))
+            {
+                return Err(Error::DuplicateEvidence);
+            }
+
+            Self::validate_tags(tags)?;
+
+            // Enforce hard size limits so a single submission cannot bloat storage
+            if title.len() > MAX_TITLE_LENGTH {
+                return Err(Error::TitleTooLong);
+            }
+            if description.len() > MAX_DESCRIPTION_LENGTH {
+                return Err(Error::DescriptionTooLong);
+            }
+            Ok(())
+        }
+
+        /// Checks a single evidence source: non-empty, within `MAX_EVIDENCE_LENGTH`
+        /// and with a citation that matches its kind
+        fn validate_evidence(source: &Evidence) -> Result<(), Error> {
+            let copy_len = source.archived_copy.as_ref().map(|copy| copy.len());
+            if source.citation.is_empty() || copy_len == Some(0) {
+                return Err(Error::EmptyEvidence);
+            }
+            if source.citation.len() > MAX_EVIDENCE_LENGTH
+                || copy_len.is_some_and(|len| len > MAX_EVIDENCE_LENGTH)
+            {
+                return Err(Error::EvidenceTooLong);
+            }
+            Self::validate_citation(source.kind, &source.citation)
+        }
+
+        /// Checks that a citation is well-formed for its evidence kind
+        fn validate_citation(kind: EvidenceKind, citation: &str) -> Result<(), Error> {
+            let valid = match kind {
+                EvidenceKind::Url => ["https://", "http://"].iter().any(|scheme| {
+                    citation.strip_prefix(scheme).is_some_and(|rest| {
+                        !rest.is_empty() && !rest.contains(char::is_whitespace)
+                    })
+                }),
+                EvidenceKind::Isbn => Self::is_valid_isbn(citation),
+                EvidenceKind::Doi => match citation.split_once('/') {
+                    Some((prefix, suffix)) => {
+                        prefix.strip_prefix("10.").is_some_and(|registrant| {
+                            !registrant.is_empty()
+                                && registrant.chars().all(|c| c.is_ascii_digit() || c == '.')
+                        }) && !suffix.is_empty()
+                            && !suffix.contains(char::is_whitespace)
+                    }
+                    None => false,
+                },
+                EvidenceKind::IpfsCid => {
+                    // CIDv0 is base58btc starting with "Qm"; CIDv1 is multibase with the base32 prefix "b"
+                    let base58 = |c: char| c.is_ascii_alphanumeric() && !"0OIl".contains(c);
+                    (citation.len() == 46 && citation.starts_with("Qm") && citation.chars().all(base58))
+                        || citation.strip_prefix('b').is_some_and(|rest| {
+                            !rest.is_empty()
+                                && rest.chars().all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+                        })
+                }
+                EvidenceKind::Archive | EvidenceKind::PrimaryDocument => true,
+            };
+
+            if valid {
+                return Ok(());
+            }
+            Err(match kind {
+                EvidenceKind::Url => Error::InvalidUrl,
+                EvidenceKind::Isbn => Error::InvalidIsbn,
+                EvidenceKind::Doi => Error::InvalidDoi,
+                _ => Error::InvalidCid,
+            })
+        }
+
+        /// Checks the length and check digit of an ISBN-10 or ISBN-13, ignoring hyphens and spaces
+        fn is_valid_isbn(isbn: &str) -> bool {
+            let chars: Vec<char> = isbn.chars().filter(|c| *c != '-' && *c != ' ').collect();
+            match chars.len() {
+                10 => {
+                    let mut sum = 0u32;
+                    for (weight, c) in (1..=10u32).rev().zip(chars.iter()) {
+                        let digit = match c.to_digit(10) {
+                            Some(digit) => digit,
+                            // Only the check digit may be X (10)
+                            None if weight == 1 && (*c == 'X' || *c == 'x') => 10,
+                            None => return false,
+                        };
+                        sum = sum.saturating_add(digit.saturating_mul(weight));
+                    }
+                    sum.is_multiple_of(11)
+                }
+                13 => {
+                    let mut sum = 0u32;
+                    for (i, c) in chars.iter().enumerate() {
+                        let Some(digit) = c.to_digit(10) else {
+                            return false;
+                        };
+                        let weight = if i % 2 == 0 { 1 } else { 3 };
+                        sum = sum.saturating_add(digit.saturating_mul(weight));
+                    }
+                    sum.is_multiple_of(10)
+                }
+                _ => false,
+            }
+        }
+
+        /// Checks a tag list: 1 to `MAX_TAGS` non-empty, distinct tags within `MAX_TAG_LENGTH`
+        fn validate_tags(tags: &[String]) -> Result<(), Error> {
+            if tags.is_empty() {
+                return Err(Error::NoTags);
+            }
+            if tags.len() > MAX_TAGS {
+                return Err(Error::TooManyTags);
+            }
+            if tags.iter().any(|tag| tag.is_empty()) {
+                return Err(Error::EmptyTag);
+            }
+            if tags.iter().any(|tag| tag.len() > MAX_TAG_LENGTH) {
+                return Err(Error::TagTooLong);
+            }
+            if tags.iter().enumerate().any(|(i, tag)| tags[..i].contains(tag)) {
+                return Err(Error::DuplicateTag);
+            }
+            Ok(())
+        }
+
+        /// Adds an event to the tag index for each of the given tags
+        /// 
+        /// Each tag's list is stored one entry per cell, so a popular tag can collect
+        /// any number of events without outgrowing a storage cell.
+        fn index_event_tags(&mut self, event_id: u64, tags: &[String]) {
+            for tag in tags.iter() {
+                let count = match self.tag_event_count.get(tag) {
+                    Some(count) => count,
+                    None => {
+                        // First time this tag is seen: register it in the tag list
+                        let index = self.known_tag_count.get_or_default();
+                        self.known_tags.insert(index, tag);
+                        self.known_tag_count.set(&index.saturating_add(1));
+                        0
+                    }
+                };
+                if !self.tag_event_index.contains((tag, event_id)) {
+                    self.tag_events.insert((tag, count), &event_id);
+                    self.tag_event_index.insert((tag, event_id), &count);
+                    self.tag_event_count.insert(tag, &count.saturating_add(1));
+                }
+            }
+        }
+
+        /// Adds an event to the timeline, user and tag indexes that listings read from
+        fn index_event(&mut self, event: &HistoricalEvent) {
+            let mut timeline_ids = self.timeline_events.get(event.timeline).unwrap_or_default();
+            timeline_ids.push(event.id);
+            self.timeline_events.insert(event.timeline, &timeline_ids);
+
+            // Keep the submitter's list in ID order when a hidden event is restored
+            let mut user_event_ids = self.user_events.get(event.submitter).unwrap_or_default();
+            let position = user_event_ids.partition_point(|&id| id < event.id);
+            user_event_ids.insert(position, event.id);
+            self.user_events.insert(event.submitter, &user_event_ids);
+
+            self.index_event_tags(event.id, &event.tags);
+        }
+
+        /// Removes an event from the timeline, user and tag indexes
+        fn unindex_event(&mut self, event: &HistoricalEvent) {
+            if let Some(mut timeline_ids) = self.timeline_events.get(event.timeline) {
+                timeline_ids.retain(|&id| id != event.id);
+                self.timeline_events.insert(event.timeline, &timeline_ids);
+            }
+
+            if let Some(mut user_event_ids) = self.user_events.get(event.submitter) {
+                user_event_ids.retain(|&id| id != event.id);
+                self.user_events.insert(event.submitter, &user_event_ids);
+            }
+
+            self.unindex_event_tags(event.id, &event.tags);
+        }
+
+        /// Removes an event from the tag index for each of the given tags
+        /// 
+        /// The last event in a tag's list is moved into the freed slot, so list
+        /// positions are not stable.
+        fn unindex_event_tags(&mut self, event_id: u64, tags: &[String]) {
+            for tag in tags.iter() {
+                let Some(index) = self.tag_event_index.get((tag, event_id)) else {
+                    continue;
+                };
+                let last = self.tag_event_count.get(tag).unwrap_or(1).saturating_sub(1);
+                if index != last {
+                    if let Some(moved) = self.tag_events.get((tag, last)) {
+                        self.tag_events.insert((tag, index), &moved);
+                        self.tag_event_index.insert((tag, moved), &index);
+                    }
+                }
+                self.tag_events.remove((tag, last));
+                self.tag_event_index.remove((tag, event_id));
+                self.tag_event_count.insert(tag, &last);
+            }
+        }
+
+        /// Reads every event ID in a tag's list
+        fn tag_event_ids(&self, tag: &String) -> Vec<u64> {
+            let count = self.tag_event_count.get(tag).unwrap_or(0);
+            (0..count)
+                .filter_map(|index| self.tag_events.get((tag, index)))
+                .collect()
+        }
+
+        /// Generates a unique event ID by incrementing the counter
+        fn generate_event_id(&mut self) -> u64 {
+            self.event_count = self.event_count.saturating_add(1);
+            self.event_count
+        }
+
+        /// Checks if a voter has already voted on a specific event
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event to check
+        /// * `voter` - The account address of the voter
+        /// 
+        /// # Returns
+        /// * `true` if the voter has already voted on this event
+        /// * `false` if the voter has not voted on this event
+        #[ink(message)]
+        pub fn has_voted(&self, event_id: u64, voter: AccountId) -> bool {
+            self.votes.get((event_id, voter)).is_some()
+        }
+
+        /// Records a vote on a historical event
+        /// 
+        /// In `TokenWeighted` mode the transferred value is staked with `Conviction::None`
+        /// and held by the contract until the vote is retracted. In `Quadratic` mode this
+        /// casts a single vote for one credit.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event to vote on
+        /// * `support` - true for support vote, false for challenge vote
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the vote was recorded successfully
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::AlreadyVoted)` if the user has already voted on this event
+        /// * `Err(Error::InsufficientStake)` if no value was transferred in `TokenWeighted` mode
+        /// * `Err(Error::UnexpectedValue)` if value was transferred outside `TokenWeighted` mode
+        /// * `Err(Error::InsufficientCredits)` if the caller has no credits left in `Quadratic` mode
+        #[ink(message, payable)]
+        pub fn vote(&mut self, event_id: u64, support: bool) -> Result<(), Error> {
+            self.ensure_writable()?;
+            if self.get_voting_mode() == VotingMode::TokenWeighted {
+                return self.vote_with_conviction(event_id, support, Conviction::None);
+            }
+
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+            let mut event = self.prepare_vote(event_id, caller, now)?;
+
+            // Only token-weighted votes carry value
+            if self.env().transferred_value() != 0 {
+                return Err(Error::UnexpectedValue);
+            }
+            if self.get_voting_mode() == VotingMode::Quadratic {
+                self.spend_vote_credits(caller, event_id, None, support, 1, now)?;
+            }
+
+            let revision = event.revision;
+            self.record_vote(&mut event, Vote {
+                voter: caller,
+                event_id,
+                support,
+                timestamp: now,
+                weight: 1,
+                stake: 0,
+                conviction: Conviction::None,
+                locked_until: now,
+                revision,
+            });
+
+            Ok(())
+        }
+
+        /// Casts a token-weighted vote whose stake is locked to multiply its weight
+        /// 
+        /// The stake stays with the vote for as long as it is cast; once the vote is
+        /// retracted it is returned when the lock expires (see `unlock`).
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event to vote on
+        /// * `support` - true for support vote, false for challenge vote
+        /// * `conviction` - Lock level; higher levels lock the stake longer for more weight
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the vote was recorded successfully
+        /// * `Err(Error::WrongVotingMode)` if the contract is not in `TokenWeighted` mode
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::AlreadyVoted)` if the user has already voted on this event
+        /// * `Err(Error::InsufficientStake)` if no value was transferred
+        #[ink(message, payable)]
+        pub fn vote_with_conviction(&mut self, event_id: u64, support: bool, conviction: Conviction) -> Result<(), Error> {
+            self.ensure_writable()?;
+            if self.get_voting_mode() != VotingMode::TokenWeighted {
+                return Err(Error::WrongVotingMode);
+            }
+
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+            let mut event = self.prepare_vote(event_id, caller, now)?;
+
+            let stake = self.env().transferred_value();
+            if stake == 0 {
+                return Err(Error::InsufficientStake);
+            }
+
+            let lock_duration = self.config().conviction_lock_period.saturating_mul(conviction.lock_periods());
+            let revision = event.revision;
+            self.record_vote(&mut event, Vote {
+                voter: caller,
+                event_id,
+                support,
+                timestamp: now,
+                weight: stake.saturating_mul(conviction.multiplier()),
+                stake,
+                conviction,
+                locked_until: now.saturating_add(lock_duration),
+                revision,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the stake of the caller's retracted votes once their locks have expired
+        /// 
+        /// Stake behind a vote that is still cast is not released here, even after its
+        /// lock has expired, because it is what gives the vote its weight. Retract the
+        /// vote to get it back; `retract_vote` refunds an expired stake directly.
+        /// 
+        /// # Returns
+        /// * `Ok(Balance)` - The amount released (0 if nothing has expired yet)
+        /// * `Err(Error::TransferFailed)` if the stake could not be transferred
+        #[ink(message)]
+        pub fn unlock(&mut self) -> Result<Balance, Error> {
+            self.ensure_writable()?;
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+
+            let mut locks = self.stake_locks.get(caller).unwrap_or_default();
+            let mut amount: Balance = 0;
+            locks.retain(|lock| {
+                if lock.locked_until <= now {
+                    amount = amount.saturating_add(lock.amount);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            if amount == 0 {
+                return Ok(0);
+            }
+
+            if locks.is_empty() {
+                self.stake_locks.remove(caller);
+            } else {
+                self.stake_locks.insert(caller, &locks);
+            }
+
+            self.env().emit_event(StakeUnlocked {
+                account: caller,
+                amount,
+            });
+
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Ok(amount)
+        }
+
+        /// Returns the still-held stake of an account's retracted votes
+        /// 
+        /// # Arguments
+        /// * `account` - The account to check
+        #[ink(message)]
+        pub fn get_stake_locks(&self, account: AccountId) -> Vec<StakeLock> {
+            self.stake_locks.get(account).unwrap_or_default()
+        }
+
+        /// Casts several votes at once in `Quadratic` mode, spending `votes²` credits
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event to vote on
+        /// * `support` - true for support votes, false for challenge votes
+        /// * `votes` - Number of votes to cast (at least 1)
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the votes were recorded successfully
+        /// * `Err(Error::WrongVotingMode)` if the contract is not in `Quadratic` mode
+        /// * `Err(Error::InvalidVoteCount)` if `votes` is 0
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::AlreadyVoted)` if the user has already voted on this event
+        /// * `Err(Error::InsufficientCredits)` if the caller cannot afford `votes²` credits
+        #[ink(message)]
+        pub fn cast_quadratic_vote(&mut self, event_id: u64, support: bool, votes: u32) -> Result<(), Error> {
+            self.ensure_writable()?;
+            if self.get_voting_mode() != VotingMode::Quadratic {
+                return Err(Error::WrongVotingMode);
+            }
+            if votes == 0 {
+                return Err(Error::InvalidVoteCount);
+            }
+
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+            let mut event = self.prepare_vote(event_id, caller, now)?;
+
+            self.spend_vote_credits(caller, event_id, None, support, votes, now)?;
+            let revision = event.revision;
+            self.record_vote(&mut event, Vote {
+                voter: caller,
+                event_id,
+                support,
+                timestamp: now,
+                weight: Balance::from(votes),
+                stake: 0,
+                conviction: Conviction::None,
+                locked_until: now,
+                revision,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the credits an account can spend in the current period
+        /// 
+        /// # Arguments
+        /// * `account` - The account to check
+        #[ink(message)]
+        pub fn get_vote_credits(&self, account: AccountId) -> u32 {
+            self.current_credits(account, self.env().block_timestamp()).balance
+        }
+
+        /// Returns a page of an account's quadratic vote spends, oldest first
+        /// 
+        /// # Arguments
+        /// * `account` - The account to check
+        /// * `cursor` - Position in the spend history to start from (0 for the first spend)
+        /// * `limit` - Maximum number of spends to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `Vec<CreditSpend>` - Continue with `cursor + returned count` until empty
+        #[ink(message)]
+        pub fn get_credit_spends(&self, account: AccountId, cursor: u32, limit: u32) -> Vec<CreditSpend> {
+            let count = self.credit_spend_count.get(account).unwrap_or(0);
+            let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (cursor..end)
+                .filter_map(|index| self.credit_spends.get((account, index)))
+                .collect()
+        }
+
+        /// Loads an account's credits, topping them up if a new period has started
+        fn current_credits(&self, account: AccountId, now: u64) -> VoteCredits {
+            let period = now.checked_div(self.config().credit_period).unwrap_or(0);
+            match self.vote_credits.get(account) {
+                Some(credits) if credits.period == period => credits,
+                _ => VoteCredits {
+                    balance: self.config().credits_per_period,
+                    period,
+                },
+            }
+        }
+
+        /// Deducts `votes²` credits from an account and records the spend
+        fn spend_vote_credits(
+            &mut self,
+            account: AccountId,
+            event_id: u64,
+            amendment_id: Option<u64>,
+            support: bool,
+            votes: u32,
+            now: u64,
+        ) -> Result<(), Error> {
+            let cost = votes.checked_mul(votes).ok_or(Error::InsufficientCredits)?;
+            let mut credits = self.current_credits(account, now);
+            credits.balance = credits.balance.checked_sub(cost).ok_or(Error::InsufficientCredits)?;
+            self.vote_credits.insert(account, &credits);
+
+            // Spends are stored one per cell, so the history can grow without limit
+            let index = self.credit_spend_count.get(account).unwrap_or(0);
+            self.credit_spends.insert((account, index), &CreditSpend {
+                event_id,
+                amendment_id,
+                support,
+                votes,
+                credits: cost,
+                timestamp: now,
+            });
+            self.credit_spend_count.insert(account, &index.saturating_add(1));
+
+            Ok(())
+        }
+
+        /// Loads an event and checks that the voter may cast a new vote on it
+        fn prepare_vote(&self, event_id: u64, voter: AccountId, now: u64) -> Result<HistoricalEvent, Error> {
+            // Validate event exists
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            // Check if user has already voted (prevent duplicates)
+            if self.has_voted(event_id, voter) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            // A retracted vote cannot be recast before the cooldown expires
+            self.ensure_vote_cooldown(event_id, voter, now)?;
+
+            Ok(event)
+        }
+
+        /// Stores a new vote and updates the event's totals, score and timeline
+        fn record_vote(&mut self, event: &mut HistoricalEvent, vote: Vote) {
+            let event_id = event.id;
+
+            // Record vote in votes mapping
+            self.votes.insert((event_id, vote.voter), &vote);
+            self.last_vote_action.insert((event_id, vote.voter), &vote.timestamp);
+            self.add_event_voter(event_id, vote.voter);
+
+            // Update event's support_votes or challenge_votes total
+            if vote.support {
+                event.support_votes = event.support_votes.saturating_add(vote.weight);
+            } else {
+                event.challenge_votes = event.challenge_votes.saturating_add(vote.weight);
+            }
+            event.voter_count = event.voter_count.saturating_add(1);
+
+            // Call calculate_consensus_score
+            self.calculate_consensus_score(event);
+
+            // Call check_timeline_movement
+            self.check_timeline_movement(event);
+
+            // Store updated event
+            self.events.insert(event_id, event);
+
+            self.env().emit_event(VoteCast {
+                event_id,
+                voter: vote.voter,
+                support: vote.support,
+                weight: vote.weight,
+                consensus_score: event.consensus_score,
+            });
+        }
+
+        /// Adds an account to the list of voters on an event
+        /// 
+        /// The list is stored one entry per cell, so it can grow with the number of
+        /// voters without outgrowing a storage cell.
+        fn add_event_voter(&mut self, event_id: u64, voter: AccountId) {
+            let count = self.event_voter_count.get(event_id).unwrap_or(0);
+            self.event_voters.insert((event_id, count), &voter);
+            self.event_voter_index.insert((event_id, voter), &count);
+            self.event_voter_count.insert(event_id, &count.saturating_add(1));
+        }
+
+        /// Removes an account from the list of voters on an event
+        /// 
+        /// The last voter is moved into the freed slot, so list positions are not stable.
+        fn remove_event_voter(&mut self, event_id: u64, voter: AccountId) {
+            let Some(index) = self.event_voter_index.get((event_id, voter)) else {
+                return;
+            };
+            let last = self.event_voter_count.get(event_id).unwrap_or(1).saturating_sub(1);
+            if index != last {
+                if let Some(moved) = self.event_voters.get((event_id, last)) {
+                    self.event_voters.insert((event_id, index), &moved);
+                    self.event_voter_index.insert((event_id, moved), &index);
+                }
+            }
+            self.event_voters.remove((event_id, last));
+            self.event_voter_index.remove((event_id, voter));
+            self.event_voter_count.insert(event_id, &last);
+        }
+
+        /// Switches the caller's existing vote between support and challenge
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event the vote was cast on
+        /// * `support` - true for support vote, false for challenge vote
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the vote was changed (or already had the requested stance)
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::NotVoted)` if the caller has not voted on this event
+        /// * `Err(Error::VoteCooldown)` if the caller changed their vote too recently
+        #[ink(message)]
+        pub fn change_vote(&mut self, event_id: u64, support: bool) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            let caller = self.env().caller();
+            let mut vote = self.votes.get((event_id, caller)).ok_or(Error::NotVoted)?;
+            if vote.support == support {
+                return Ok(());
+            }
+
+            let now = self.env().block_timestamp();
+            self.ensure_vote_cooldown(event_id, caller, now)?;
+
+            // Move the vote's weight from one side to the other
+            if support {
+                event.challenge_votes = event.challenge_votes.saturating_sub(vote.weight);
+                event.support_votes = event.support_votes.saturating_add(vote.weight);
+            } else {
+                event.support_votes = event.support_votes.saturating_sub(vote.weight);
+                event.challenge_votes = event.challenge_votes.saturating_add(vote.weight);
+            }
+
+            vote.support = support;
+            vote.timestamp = now;
+            vote.revision = event.revision;
+            self.votes.insert((event_id, caller), &vote);
+            self.last_vote_action.insert((event_id, caller), &now);
+
+            self.calculate_consensus_score(&mut event);
+            self.check_timeline_movement(&mut event);
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(VoteChanged {
+                event_id,
+                voter: caller,
+                support,
+                consensus_score: event.consensus_score,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraws the caller's vote on a historical event, along with the caller's
+        /// ratings of its evidence
+        /// 
+        /// Any stake is refunded immediately, or kept until its conviction lock
+        /// expires and then released through `unlock`.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event the vote was cast on
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the vote was removed
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::NotVoted)` if the caller has not voted on this event
+        /// * `Err(Error::VoteCooldown)` if the caller changed their vote too recently
+        /// * `Err(Error::TransferFailed)` if the stake could not be refunded
+        #[ink(message)]
+        pub fn retract_vote(&mut self, event_id: u64) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            let caller = self.env().caller();
+            let vote = self.votes.get((event_id, caller)).ok_or(Error::NotVoted)?;
+
+            let now = self.env().block_timestamp();
+            self.ensure_vote_cooldown(event_id, caller, now)?;
+
+            if vote.support {
+                event.support_votes = event.support_votes.saturating_sub(vote.weight);
+            } else {
+                event.challenge_votes = event.challenge_votes.saturating_sub(vote.weight);
+            }
+            event.voter_count = event.voter_count.saturating_sub(1);
+
+            self.votes.remove((event_id, caller));
+            self.last_vote_action.insert((event_id, caller), &now);
+            self.remove_event_voter(event_id, caller);
+            self.withdraw_evidence_ratings(&mut event, caller);
+
+            self.calculate_consensus_score(&mut event);
+            self.check_timeline_movement(&mut event);
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(VoteRetracted {
+                event_id,
+                voter: caller,
+                consensus_score: event.consensus_score,
+            });
+
+            if vote.stake > 0 {
+                if vote.locked_until > now {
+                    let mut locks = self.stake_locks.get(caller).unwrap_or_default();
+                    locks.push(StakeLock {
+                        amount: vote.stake,
+                        locked_until: vote.locked_until,
+                    });
+                    self.stake_locks.insert(caller, &locks);
+                } else {
+                    self.env()
+                        .transfer(caller, vote.stake)
+                        .map_err(|_| Error::TransferFailed)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Settles an event's submission deposit once its review window has closed
+        /// 
+        /// Callable by anyone. The deposit is refunded if the event is Canonical,
+        /// Disputed or a low-confidence Alternative; an Alternative event at or below
+        /// `slash_score_threshold` has `slash_percent` of it sent to the treasury. An
+        /// event that is still Pending is refunded once `pending_timeout` has passed.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The ID of the event whose deposit to settle
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the deposit was settled
+        /// * `Err(Error::EventNotFound)` if the event doesn't exist
+        /// * `Err(Error::NoDeposit)` if no deposit was paid for the event
+        /// * `Err(Error::DepositAlreadySettled)` if the deposit was already settled
+        /// * `Err(Error::ReviewWindowOpen)` if the review window has not closed yet
+        /// * `Err(Error::EventStillPending)` if the event has not reached quorum and
+        ///   `pending_timeout` has not passed
+        /// * `Err(Error::TransferFailed)` if a payout could not be transferred
+        #[ink(message)]
+        pub fn settle_deposit(&mut self, event_id: u64) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let mut deposit = self.deposits.get(event_id).ok_or(Error::NoDeposit)?;
+            if deposit.status != DepositStatus::Held {
+                return Err(Error::DepositAlreadySettled);
+            }
+
+            let config = self.config();
+            let now = self.env().block_timestamp();
+            if now < event.created_at.saturating_add(config.review_window) {
+                return Err(Error::ReviewWindowOpen);
+            }
+
+            let slashed = match event.timeline {
+                Timeline::Pending => {
+                    if now < event.created_at.saturating_add(config.pending_timeout) {
+                        return Err(Error::EventStillPending);
+                    }
+                    0
+                }
+                Timeline::Alternative if event.consensus_score <= config.slash_score_threshold => {
+                    deposit.amount.saturating_mul(Balance::from(config.slash_percent)) / 100
+                }
+                _ => 0,
+            };
+            let refunded = deposit.amount.saturating_sub(slashed);
+
+            deposit.status = if slashed > 0 {
+                DepositStatus::Slashed
+            } else {
+                DepositStatus::Refunded
+            };
+            deposit.slashed = slashed;
+            self.deposits.insert(event_id, &deposit);
+
+            self.env().emit_event(DepositSettled {
+                event_id,
+                submitter: event.submitter,
+                refunded,
+                slashed,
+            });
+
+            if slashed > 0 {
+                self.env()
+                    .transfer(self.get_treasury(), slashed)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            if refunded > 0 {
+                self.env()
+                    .transfer(event.submitter, refunded)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+
+            Ok(())
+        }
+
+        /// Retrieves the submission deposit of an event
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event
+        /// 
+        /// # Returns
+        /// * `Some(SubmissionDeposit)` if a deposit was paid
+        /// * `None` if the event doesn't exist or was submitted without a deposit
+        #[ink(message)]
+        pub fn get_deposit(&self, event_id: u64) -> Option<SubmissionDeposit> {
+            self.deposits.get(event_id)
+        }
+
+        /// Returns the account that receives slashed deposits
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury.get().unwrap_or(AccountId::from([0; 32]))
+        }
+
+        /// Changes the account that receives slashed deposits
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.treasury.set(&treasury);
+            Ok(())
+        }
+
+        /// Returns the voting mode chosen at instantiation
+        #[ink(message)]
+        pub fn get_voting_mode(&self) -> VotingMode {
+            self.voting_mode.get().unwrap_or(VotingMode::OneAccountOneVote)
+        }
+
+        /// Returns the current protocol parameters
+        #[ink(message)]
+        pub fn get_config(&self) -> Config {
+            self.config()
+        }
+
+        /// Replaces the protocol parameters
+        /// 
+        /// New thresholds apply to an event the next time its votes change.
+        /// 
+        /// # Arguments
+        /// * `config` - The new protocol parameters
+        /// 
+        /// # Returns
+        /// * `Ok(())` if the parameters were updated
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        /// * `Err(Error::InvalidConfig)` if the thresholds or quorum are inconsistent
+        #[ink(message)]
+        pub fn set_config(&mut self, config: Config) -> Result<(), Error> {
+            let caller = self.ensure_owner()?;
+            config.validate()?;
+
+            self.config.set(&config);
+
+            self.env().emit_event(ConfigChanged {
+                changed_by: caller,
+                config,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the current owner, or `None` once ownership has been renounced
+        #[ink(message)]
+        pub fn owner(&self) -> Option<AccountId> {
+            self.owner.get().flatten()
+        }
+
+        /// Returns the account nominated by `transfer_ownership`, if any
+        #[ink(message)]
+        pub fn pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner.get().flatten()
+        }
+
+        /// Nominates a new owner, who must call `accept_ownership` to take over
+        /// 
+        /// Nominating again replaces any earlier nomination.
+        /// 
+        /// # Arguments
+        /// * `new_owner` - The account to hand ownership to
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.pending_owner.set(&Some(new_owner));
+            Ok(())
+        }
+
+        /// Completes an ownership transfer started with `transfer_ownership`
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the pending owner
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.pending_owner() != Some(caller) {
+                return Err(Error::Unauthorized);
+            }
+
+            let previous_owner = self.owner();
+            self.owner.set(&Some(caller));
+            self.pending_owner.set(&None);
+
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner: Some(caller),
+            });
+
+            Ok(())
+        }
+
+        /// Gives up ownership permanently, leaving the protocol parameters frozen
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<(), Error> {
+            let caller = self.ensure_owner()?;
+            self.owner.set(&None);
+            self.pending_owner.set(&None);
+
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner: Some(caller),
+                new_owner: None,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the caller if it is the owner, `Error::Unauthorized` otherwise
+        fn ensure_owner(&self) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if self.owner() != Some(caller) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(caller)
+        }
+
+        /// Checks whether an account holds a role
+        #[ink(message)]
+        pub fn has_role(&self, account: AccountId, role: Role) -> bool {
+            self.roles.contains((account, role))
+        }
+
+        /// Grants a role to an account
+        /// 
+        /// The owner can grant any role; admins can grant every role except `Admin`.
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller may not manage this role
+        #[ink(message)]
+        pub fn grant_role(&mut self, account: AccountId, role: Role) -> Result<(), Error> {
+            let caller = self.ensure_role_manager(role)?;
+            if !self.has_role(account, role) {
+                self.roles.insert((account, role), &());
+                self.env().emit_event(RoleGranted {
+                    account,
+                    role,
+                    granted_by: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// Revokes a role from an account
+        /// 
+        /// The owner can revoke any role; admins can revoke every role except `Admin`.
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller may not manage this role
+        #[ink(message)]
+        pub fn revoke_role(&mut self, account: AccountId, role: Role) -> Result<(), Error> {
+            let caller = self.ensure_role_manager(role)?;
+            if self.has_role(account, role) {
+                self.roles.remove((account, role));
+                self.env().emit_event(RoleRevoked {
+                    account,
+                    role,
+                    revoked_by: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// Returns the caller if it may grant and revoke `role`
+        fn ensure_role_manager(&self, role: Role) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            let is_owner = self.owner() == Some(caller);
+            if is_owner || (role != Role::Admin && self.has_role(caller, Role::Admin)) {
+                Ok(caller)
+            } else {
+                Err(Error::Unauthorized)
+            }
+        }
+
+        /// Returns the caller if it holds `role`, `Error::Unauthorized` otherwise
+        fn ensure_role(&self, role: Role) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if !self.has_role(caller, role) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(caller)
+        }
+
+        /// Returns whether the contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused.get_or_default()
+        }
+
+        /// Stops submissions, voting, stake and deposit movements, tag edits and
+        /// attestations until `unpause` is called (owner or admins only)
+        /// 
+        /// Queries, moderation and administrative messages keep working.
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is neither the owner nor an admin
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            let caller = self.ensure_admin()?;
+            if !self.is_paused() {
+                self.paused.set(&true);
+                self.env().emit_event(Paused { account: caller });
+            }
+            Ok(())
+        }
+
+        /// Lifts a pause started with `pause` (owner or admins only)
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is neither the owner nor an admin
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            let caller = self.ensure_admin()?;
+            if self.is_paused() {
+                self.paused.set(&false);
+                self.env().emit_event(Unpaused { account: caller });
+            }
+            Ok(())
+        }
+
+        /// Returns the caller if it is the owner or an admin, `Error::Unauthorized` otherwise
+        fn ensure_admin(&self) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if self.owner() != Some(caller) && !self.has_role(caller, Role::Admin) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(caller)
+        }
+
+        /// Fails with `Error::Paused` while the contract is paused, and with
+        /// `Error::MigrationPending` until storage has been migrated after an upgrade
+        fn ensure_writable(&self) -> Result<(), Error> {
+            if self.is_paused() {
+                return Err(Error::Paused);
+            }
+            if self.get_storage_version() != STORAGE_VERSION {
+                return Err(Error::MigrationPending);
+            }
+            Ok(())
+        }
+
+        /// Returns the storage layout version the stored data is in
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u32 {
+            self.storage_version.get_or_default()
+        }
+
+        /// Replaces the contract code while keeping the address and storage (owner only)
+        /// 
+        /// If the new code uses a newer storage layout, call `migrate` right after;
+        /// mutating messages fail with `Error::MigrationPending` until then.
+        /// 
+        /// # Arguments
+        /// * `code_hash` - Hash of code already uploaded to the chain
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        /// * `Err(Error::UpgradeFailed)` if no code with that hash has been uploaded
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            let caller = self.ensure_owner()?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)?;
+
+            self.env().emit_event(CodeUpgraded {
+                code_hash,
+                upgraded_by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Brings storage written by older code forward to `STORAGE_VERSION` (owner only)
+        /// 
+        /// Migrations run one version at a time and convert at most `limit` items per
+        /// call (capped at `MAX_PAGE_SIZE`), so call this repeatedly until it returns
+        /// `STORAGE_VERSION`; calling it on current storage is a no-op.
+        /// 
+        /// Version 0 code had no owner, so on version 0 storage `VERSION_0_OWNER` becomes
+        /// the owner and treasury. Version 0 votes cannot be enumerated on-chain; move
+        /// them with `migrate_votes`, and drop any whose voters cannot be found with
+        /// `discard_unmigrated_votes`, before the final call.
+        /// 
+        /// # Arguments
+        /// * `limit` - Maximum number of events to convert in this call
+        /// 
+        /// # Returns
+        /// * `Ok(u32)` - The storage version after this call
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        /// * `Err(Error::UnsupportedStorageVersion)` if storage was written by newer code
+        #[ink(message)]
+        pub fn migrate(&mut self, limit: u32) -> Result<u32, Error> {
+            let from_version = self.get_storage_version();
+            if from_version == 0 && self.owner().is_none() {
+                let owner = AccountId::from(VERSION_0_OWNER);
+                self.owner.set(&Some(owner));
+                self.treasury.set(&owner);
+            }
+            self.ensure_owner()?;
+            if from_version > STORAGE_VERSION {
+                return Err(Error::UnsupportedStorageVersion);
+            }
+
+            let mut version = from_version;
+            while version < STORAGE_VERSION && self.migrate_step(version, limit)? {
+                version = version.saturating_add(1);
+                self.storage_version.set(&version);
+            }
+
+            if from_version != version {
+                self.env().emit_event(StorageMigrated {
+                    from_version,
+                    to_version: version,
+                });
+            }
+
+            Ok(version)
+        }
+
+        /// Rewrites stored data from layout `from_version` towards `from_version + 1`
+        /// 
+        /// Returns whether the step has finished.
+        fn migrate_step(&mut self, from_version: u32, limit: u32) -> Result<bool, Error> {
+            match from_version {
+                0 => Ok(self.migrate_from_version_0(limit)),
+                _ => Err(Error::UnsupportedStorageVersion),
+            }
+        }
+
+        /// Converts up to `limit` version 0 events to the current layout
+        /// 
+        /// Finishes once every event is converted and each vote they were counted with
+        /// has been moved by `migrate_votes` or dropped by `discard_unmigrated_votes`.
+        fn migrate_from_version_0(&mut self, limit: u32) -> bool {
+            let mut event_id = self.migration_cursor.get_or_default().max(1);
+            let end = event_id.saturating_add(u64::from(limit.min(MAX_PAGE_SIZE)));
+            let mut votes_remaining = self.legacy_votes_remaining.get_or_default();
+
+            while event_id < end && event_id <= self.event_count {
+                if let Some(legacy) = self.legacy_events.take(event_id) {
+                    let tally = LegacyVoteTally {
+                        support: legacy.support_votes,
+                        challenge: legacy.challenge_votes,
+                    };
+                    let event = Self::upgrade_legacy_event(legacy);
+                    // Timeline and user indexes kept their layout; tags were not indexed yet
+                    self.index_event_tags(event.id, &event.tags);
+                    votes_remaining = votes_remaining.saturating_add(u64::from(event.voter_count));
+                    if event.voter_count > 0 {
+                        self.legacy_vote_tallies.insert(event.id, &tally);
+                    }
+                    self.events.insert(event.id, &event);
+                }
+                event_id = event_id.saturating_add(1);
+            }
+
+            self.migration_cursor.set(&event_id);
+            self.legacy_votes_remaining.set(&votes_remaining);
+            event_id > self.event_count && votes_remaining == 0
+        }
+
+        /// Converts a version 0 event to the current layout
+        /// 
+        /// Plain-text sources become `Url` evidence when they are well-formed URLs and
+        /// `Archive` citations otherwise. Every version 0 vote weighed 1.
+        fn upgrade_legacy_event(legacy: LegacyEvent) -> HistoricalEvent {
+            let evidence_sources = legacy
+                .evidence_sources
+                .into_iter()
+                .map(|citation| Evidence {
+                    kind: if Self::validate_citation(EvidenceKind::Url, &citation).is_ok() {
+                        EvidenceKind::Url
+                    } else {
+                        EvidenceKind::Archive
+                    },
+                    citation,
+                    content_hash: None,
+                    archived_copy: None,
+                })
+                .collect();
+
+            HistoricalEvent {
+                id: legacy.id,
+                title: legacy.title,
+                date: legacy.date,
+                description: legacy.description,
+                evidence_sources,
+                tags: legacy.tags,
+                submitter: legacy.submitter,
+                timeline: legacy.timeline,
+                consensus_score: legacy.consensus_score,
+                support_votes: Balance::from(legacy.support_votes),
+                challenge_votes: Balance::from(legacy.challenge_votes),
+                voter_count: legacy.support_votes.saturating_add(legacy.challenge_votes),
+                created_at: legacy.created_at,
+                hidden: false,
+                revision: 0,
+                substantive_revision: 0,
+                evidence_quality_score: None,
+            }
+        }
+
+        /// Moves version 0 votes on a converted event to the current layout (owner only)
+        /// 
+        /// Version 0 stored votes only under (event, voter) keys, so the voters have to be
+        /// supplied; they can be read from the `vote` calls made against the old code.
+        /// Migrated votes weigh 1 and hold no stake, as they did in version 0.
+        /// 
+        /// # Arguments
+        /// * `event_id` - An event already converted by `migrate`
+        /// * `voters` - Up to `MAX_PAGE_SIZE` accounts that voted on it
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        /// * `Err(Error::UnsupportedStorageVersion)` if storage is not at version 0
+        /// * `Err(Error::BatchTooLarge)` if more than `MAX_PAGE_SIZE` voters are given
+        /// * `Err(Error::EventNotFound)` if the event has not been converted yet
+        /// * `Err(Error::NotVoted)` if an account has no version 0 vote on the event
+        #[ink(message)]
+        pub fn migrate_votes(&mut self, event_id: u64, voters: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if self.get_storage_version() != 0 {
+                return Err(Error::UnsupportedStorageVersion);
+            }
+            if voters.len() > MAX_PAGE_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+            if !self.events.contains(event_id) {
+                return Err(Error::EventNotFound);
+            }
+
+            let mut votes_remaining = self.legacy_votes_remaining.get_or_default();
+            let mut tally = self.legacy_vote_tallies.get(event_id).unwrap_or_default();
+            for voter in voters {
+                let legacy = self.legacy_votes.take((event_id, voter)).ok_or(Error::NotVoted)?;
+                if legacy.support {
+                    tally.support = tally.support.saturating_sub(1);
+                } else {
+                    tally.challenge = tally.challenge.saturating_sub(1);
+                }
+                let vote = Vote {
+                    voter,
+                    event_id,
+                    support: legacy.support,
+                    timestamp: legacy.timestamp,
+                    weight: 1,
+                    stake: 0,
+                    conviction: Conviction::None,
+                    locked_until: legacy.timestamp,
+                    revision: 0,
+                };
+                self.votes.insert((event_id, voter), &vote);
+                self.last_vote_action.insert((event_id, voter), &vote.timestamp);
+                self.add_event_voter(event_id, voter);
+                votes_remaining = votes_remaining.saturating_sub(1);
+            }
+            self.legacy_votes_remaining.set(&votes_remaining);
+            self.legacy_vote_tallies.insert(event_id, &tally);
+
+            Ok(())
+        }
+
+        /// Drops the version 0 votes on an event that `migrate_votes` has not moved (owner only)
+        /// 
+        /// For voters that cannot be found. The event's tallies are recounted from the
+        /// votes migrated so far and its timeline re-evaluated, so the migration can finish.
+        /// 
+        /// # Arguments
+        /// * `event_id` - An event already converted by `migrate`
+        /// 
+        /// # Returns
+        /// * `Ok(u32)` - The number of votes dropped
+        /// * `Err(Error::Unauthorized)` if the caller is not the owner
+        /// * `Err(Error::UnsupportedStorageVersion)` if storage is not at version 0
+        /// * `Err(Error::EventNotFound)` if the event has not been converted yet
+        #[ink(message)]
+        pub fn discard_unmigrated_votes(&mut self, event_id: u64) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            if self.get_storage_version() != 0 {
+                return Err(Error::UnsupportedStorageVersion);
+            }
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let Some(tally) = self.legacy_vote_tallies.take(event_id) else {
+                return Ok(0);
+            };
+
+            let dropped = tally.support.saturating_add(tally.challenge);
+            event.support_votes = event.support_votes.saturating_sub(Balance::from(tally.support));
+            event.challenge_votes = event.challenge_votes.saturating_sub(Balance::from(tally.challenge));
+            event.voter_count = event.voter_count.saturating_sub(dropped);
+            self.calculate_consensus_score(&mut event);
+            self.check_timeline_movement(&mut event);
+            self.events.insert(event_id, &event);
+
+            let votes_remaining = self.legacy_votes_remaining.get_or_default();
+            self.legacy_votes_remaining.set(&votes_remaining.saturating_sub(u64::from(dropped)));
+
+            Ok(dropped)
+        }
+
+        /// Hides an event from listings or makes it visible again (moderators only)
+        /// 
+        /// Hiding takes the event out of the timeline, user and tag indexes, so it is left
+        /// out of every listing, query, page total and tag count. It can still be fetched
+        /// with `get_event` and voted on, and is re-indexed under its current timeline and
+        /// tags when restored.
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not a moderator
+        /// * `Err(Error::EventNotFound)` if the event does not exist
+        #[ink(message)]
+        pub fn set_event_hidden(&mut self, event_id: u64, hidden: bool) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Moderator)?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            if event.hidden != hidden {
+                if hidden {
+                    self.unindex_event(&event);
+                } else {
+                    self.index_event(&event);
+                }
+                event.hidden = hidden;
+                self.events.insert(event_id, &event);
+                self.env().emit_event(EventVisibilityChanged {
+                    event_id,
+                    moderator: caller,
+                    hidden,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Replaces the tags of an event and updates the tag index (curators only)
+        /// 
+        /// The change is recorded as a revision authored by the curator. It does not
+        /// outdate votes, and open amendments that leave the tags alone keep the new ones.
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not a curator
+        /// * `Err(Error::EventNotFound)` if the event does not exist
+        /// * Any tag validation error `submit_event` would return
+        #[ink(message)]
+        pub fn set_event_tags(&mut self, event_id: u64, tags: Vec<String>) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let caller = self.ensure_role(Role::Curator)?;
+            Self::validate_tags(&tags)?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if event.tags == tags {
+                return Ok(());
+            }
+
+            let revision = EventRevision {
+                revision: 0,
+                title: event.title.clone(),
+                description: event.description.clone(),
+                evidence_sources: event.evidence_sources.clone(),
+                tags: tags.clone(),
+                author: caller,
+                timestamp: self.env().block_timestamp(),
+                substantive: false,
+            };
+            self.apply_revision(&mut event, revision);
+
+            self.env().emit_event(EventTagsChanged {
+                event_id,
+                curator: caller,
+                tags,
+            });
+
+            Ok(())
+        }
+
+        /// Attests that one of an event's evidence sources checks out (verifiers only)
+        /// 
+        /// Attestations follow the source itself rather than its position, so a revision
+        /// that replaces the source leaves them behind.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event the evidence belongs to
+        /// * `evidence_index` - Position of the source in `evidence_sources`
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is not a verifier
+        /// * `Err(Error::EventNotFound)` if the event does not exist
+        /// * `Err(Error::EvidenceNotFound)` if the index is out of range
+        /// * `Err(Error::AlreadyAttested)` if the caller already attested this source
+        #[ink(message)]
+        pub fn attest_evidence(&mut self, event_id: u64, evidence_index: u32) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let caller = self.ensure_role(Role::Verifier)?;
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let source = event
+                .evidence_sources
+                .get(evidence_index as usize)
+                .ok_or(Error::EvidenceNotFound)?;
+            let key = self.evidence_key(event_id, source);
+
+            let mut attestations = self.evidence_attestations.get(key).unwrap_or_default();
+            if attestations.iter().any(|a| a.verifier == caller) {
+                return Err(Error::AlreadyAttested);
+            }
+            attestations.push(EvidenceAttestation {
+                evidence_index,
+                verifier: caller,
+                timestamp: self.env().block_timestamp(),
+            });
+            self.evidence_attestations.insert(key, &attestations);
+
+            self.env().emit_event(EvidenceAttested {
+                event_id,
+                verifier: caller,
+                evidence_index,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the attestations of an event's current evidence sources, in source
+        /// order and oldest first for each source
+        #[ink(message)]
+        pub fn get_evidence_attestations(&self, event_id: u64) -> Vec<EvidenceAttestation> {
+            let Some(event) = self.events.get(event_id) else {
+                return Vec::new();
+            };
+            let mut attestations = Vec::new();
+            for (index, source) in (0u32..).zip(event.evidence_sources.iter()) {
+                let key = self.evidence_key(event_id, source);
+                for mut attestation in self.evidence_attestations.get(key).unwrap_or_default() {
+                    // Stored with the position at the time; report where the source is now
+                    attestation.evidence_index = index;
+                    attestations.push(attestation);
+                }
+            }
+            attestations
+        }
+
+        /// Attaches a new evidence source to an existing event
+        /// 
+        /// Open to any account, limited to one call per `evidence_cooldown`. The source is
+        /// appended as a new revision authored by the caller, which doesn't supersede open
+        /// amendments. Verifiers can mark a source significant, making it a substantive
+        /// revision so votes cast before it are flagged as outdated.
+        /// 
+        /// The call requires `evidence_deposit` plus `deposit_per_byte` for each byte of the
+        /// stored revision. It is settled with `settle_evidence_deposit` once the review window
+        /// has passed, so voters can rate the source first.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event to add the source to
+        /// * `evidence` - The new source
+        /// * `significant` - Whether the source could change how the event should be judged
+        /// 
+        /// # Returns
+        /// * `Ok(u32)` - The index of the new source in `evidence_sources`
+        /// * `Err(Error::EventNotFound)` if the event does not exist
+        /// * `Err(Error::Unauthorized)` if `significant` is set by an account without the Verifier role
+        /// * `Err(Error::EvidenceCooldown)` if the caller added evidence too recently
+        /// * `Err(Error::TooManyEvidence)` if the event already has `MAX_EVIDENCE_SOURCES` sources
+        /// * `Err(Error::DuplicateEvidence)` if the event already cites this source
+        /// * `Err(Error::InsufficientDeposit)` if the transferred value is below the deposit
+        /// * Any evidence validation error `submit_event` would return
+        #[ink(message, payable)]
+        pub fn add_evidence(&mut self, event_id: u64, evidence: Evidence, significant: bool) -> Result<u32, Error> {
+            self.ensure_writable()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+
+            if significant && !self.has_role(caller, Role::Verifier) {
+                return Err(Error::Unauthorized);
+            }
+
+            if let Some(last_added) = self.last_evidence_added.get(caller) {
+                if now < last_added.saturating_add(self.config().evidence_cooldown) {
+                    return Err(Error::EvidenceCooldown);
+                }
+            }
+            if event.evidence_sources.len() >= MAX_EVIDENCE_SOURCES {
+                return Err(Error::TooManyEvidence);
+            }
+            Self::validate_evidence(&evidence)?;
+            if event.evidence_sources.contains(&evidence) {
+                return Err(Error::DuplicateEvidence);
+            }
+
+            let evidence_index = u32::try_from(event.evidence_sources.len()).unwrap_or(u32::MAX);
+            let evidence_key = self.evidence_key(event_id, &evidence);
+            let mut evidence_sources = event.evidence_sources.clone();
+            evidence_sources.push(evidence);
+
+            let content = EventRevision {
+                revision: 0,
+                title: event.title.clone(),
+                description: event.description.clone(),
+                evidence_sources,
+                tags: event.tags.clone(),
+                author: caller,
+                timestamp: now,
+                substantive: significant,
+            };
+            let deposit = self.env().transferred_value();
+            let required = self
+                .config()
+                .evidence_deposit
+                .saturating_add(self.revision_deposit(&event, &content));
+            if deposit < required {
+                return Err(Error::InsufficientDeposit);
+            }
+            let revision = self.apply_revision(&mut event, content);
+
+            let mut contributions = self.evidence_contributions.get(event_id).unwrap_or_default();
+            contributions.push(EvidenceContribution {
+                evidence_index,
+                contributor: caller,
+                timestamp: now,
+                significant,
+                revision,
+                evidence_key,
+                deposit,
+                deposit_status: DepositStatus::Held,
+            });
+            self.evidence_contributions.insert(event_id, &contributions);
+            self.last_evidence_added.insert(caller, &now);
+
+            self.env().emit_event(EvidenceAdded {
+                event_id,
+                contributor: caller,
+                evidence_index,
+                significant,
+                revision,
+            });
+
+            Ok(evidence_index)
+        }
+
+        /// Returns the evidence sources added to an event after submission, oldest first
+        #[ink(message)]
+        pub fn get_evidence_contributions(&self, event_id: u64) -> Vec<EvidenceContribution> {
+            self.evidence_contributions.get(event_id).unwrap_or_default()
+        }
+
+        /// Settles the deposit paid for an added evidence source once the review window
+        /// has closed
+        /// 
+        /// Callable by anyone. If the source's rating score has fallen to
+        /// `slash_score_threshold` or below, `slash_percent` of the deposit is sent to the
+        /// treasury; otherwise, including when nobody rated it, it is refunded in full.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event the source was added to
+        /// * `contribution_index` - Position of the contribution in `get_evidence_contributions`
+        /// 
+        /// # Returns
+        /// * `Err(Error::EvidenceNotFound)` if there is no such contribution
+        /// * `Err(Error::NoDeposit)` if no deposit was paid for it
+        /// * `Err(Error::DepositAlreadySettled)` if the deposit was already settled
+        /// * `Err(Error::ReviewWindowOpen)` if the review window has not closed yet
+        /// * `Err(Error::TransferFailed)` if a payout could not be transferred
+        #[ink(message)]
+        pub fn settle_evidence_deposit(&mut self, event_id: u64, contribution_index: u32) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut contributions = self.evidence_contributions.get(event_id).unwrap_or_default();
+            let contribution = contributions
+                .get_mut(contribution_index as usize)
+                .ok_or(Error::EvidenceNotFound)?;
+            if contribution.deposit == 0 {
+                return Err(Error::NoDeposit);
+            }
+            if contribution.deposit_status != DepositStatus::Held {
+                return Err(Error::DepositAlreadySettled);
+            }
+            let config = self.config();
+            let now = self.env().block_timestamp();
+            if now < contribution.timestamp.saturating_add(config.review_window) {
+                return Err(Error::ReviewWindowOpen);
+            }
+
+            let score = self
+                .evidence_tallies
+                .get(contribution.evidence_key)
+                .and_then(|tally| tally.score());
+            let slashed = match score {
+                Some(score) if score <= config.slash_score_threshold => {
+                    contribution.deposit.saturating_mul(Balance::from(config.slash_percent)) / 100
+                }
+                _ => 0,
+            };
+            let refunded = contribution.deposit.saturating_sub(slashed);
+            contribution.deposit_status = if slashed > 0 {
+                DepositStatus::Slashed
+            } else {
+                DepositStatus::Refunded
+            };
+            let contributor = contribution.contributor;
+            let evidence_index = contribution.evidence_index;
+            self.evidence_contributions.insert(event_id, &contributions);
+
+            self.env().emit_event(EvidenceDepositSettled {
+                event_id,
+                contributor,
+                evidence_index,
+                refunded,
+                slashed,
+            });
+
+            if slashed > 0 {
+                self.env()
+                    .transfer(self.get_treasury(), slashed)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            if refunded > 0 {
+                self.env()
+                    .transfer(contributor, refunded)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+
+            Ok(())
+        }
+
+        /// Rates one of an event's evidence sources as credible, weak or fabricated
+        /// 
+        /// Only accounts with a vote on the event can rate its evidence, and each rating
+        /// counts with the weight of that vote, so ratings are as costly to accumulate as
+        /// votes in every voting mode. Retracting the vote withdraws the ratings.
+        /// 
+        /// Ratings follow the source itself rather than its position, so they survive
+        /// revisions that reorder the sources; rating again replaces the earlier rating.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event the evidence belongs to
+        /// * `evidence_index` - Position of the source in `evidence_sources`
+        /// * `rating` - The caller's judgement of the source
+        /// 
+        /// # Returns
+        /// * `Err(Error::EventNotFound)` if the event does not exist
+        /// * `Err(Error::EvidenceNotFound)` if the index is out of range
+        /// * `Err(Error::NotVoted)` if the caller has no vote on the event
+        #[ink(message)]
+        pub fn rate_evidence(&mut self, event_id: u64, evidence_index: u32, rating: EvidenceRating) -> Result<(), Error> {
+            self.ensure_writable()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let source = event
+                .evidence_sources
+                .get(evidence_index as usize)
+                .ok_or(Error::EvidenceNotFound)?;
+            let key = self.evidence_key(event_id, source);
+            let caller = self.env().caller();
+            let weight = self.votes.get((event_id, caller)).ok_or(Error::NotVoted)?.weight;
+
+            let mut tally = self.evidence_tallies.get(key).unwrap_or_default();
+            if let Some(previous) = self.evidence_ratings.get((key, caller)) {
+                let count = tally.count_mut(previous.rating);
+                *count = count.saturating_sub(previous.weight);
+            }
+            let count = tally.count_mut(rating);
+            *count = count.saturating_add(weight);
+            self.evidence_tallies.insert(key, &tally);
+            self.evidence_ratings.insert((key, caller), &WeightedRating { rating, weight });
+
+            self.update_evidence_quality(&mut event);
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(EvidenceRated {
+                event_id,
+                rater: caller,
+                evidence_index,
+                rating,
+                evidence_quality_score: event.evidence_quality_score,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the rating counts for each of an event's current evidence sources, in order
+        #[ink(message)]
+        pub fn get_evidence_tallies(&self, event_id: u64) -> Vec<EvidenceTally> {
+            match self.events.get(event_id) {
+                Some(event) => event
+                    .evidence_sources
+                    .iter()
+                    .map(|source| {
+                        self.evidence_tallies
+                            .get(self.evidence_key(event_id, source))
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+                None => Vec::new(),
+            }
+        }
+
+        /// Returns how an account rated one of an event's evidence sources, if it did
+        #[ink(message)]
+        pub fn get_evidence_rating(&self, event_id: u64, evidence_index: u32, rater: AccountId) -> Option<EvidenceRating> {
+            let event = self.events.get(event_id)?;
+            let source = event.evidence_sources.get(evidence_index as usize)?;
+            self.evidence_ratings
+                .get((self.evidence_key(event_id, source), rater))
+                .map(|weighted| weighted.rating)
+        }
+
+        /// Removes a rater's ratings from the tallies of an event's current evidence sources
+        fn withdraw_evidence_ratings(&mut self, event: &mut HistoricalEvent, rater: AccountId) {
+            for source in event.evidence_sources.iter() {
+                let key = self.evidence_key(event.id, source);
+                if let Some(weighted) = self.evidence_ratings.take((key, rater)) {
+                    let mut tally = self.evidence_tallies.get(key).unwrap_or_default();
+                    let count = tally.count_mut(weighted.rating);
+                    *count = count.saturating_sub(weighted.weight);
+                    self.evidence_tallies.insert(key, &tally);
+                }
+            }
+            self.update_evidence_quality(event);
+        }
+
+        /// Identifies an evidence source on an event by the BLAKE2-256 hash of both encoded
+        fn evidence_key(&self, event_id: u64, evidence: &Evidence) -> Hash {
+            Hash::from(self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(event_id, evidence)))
+        }
+
+        /// Recomputes an event's evidence quality score as the average of its rated sources
+        fn update_evidence_quality(&self, event: &mut HistoricalEvent) {
+            let scores: Vec<u32> = event
+                .evidence_sources
+                .iter()
+                .filter_map(|source| {
+                    self.evidence_tallies
+                        .get(self.evidence_key(event.id, source))
+                        .and_then(|tally| tally.score())
+                })
+                .map(u32::from)
+                .collect();
+
+            event.evidence_quality_score = if scores.is_empty() {
+                None
+            } else {
+                let count = u32::try_from(scores.len()).unwrap_or(u32::MAX);
+                scores.iter().sum::<u32>().checked_div(count).and_then(|score| u8::try_from(score).ok())
+            };
+        }
+
+        /// Fails with `Error::VoteCooldown` if the voter acted on this event too recently
+        fn ensure_vote_cooldown(&self, event_id: u64, voter: AccountId, now: u64) -> Result<(), Error> {
+            if let Some(last_action) = self.last_vote_action.get((event_id, voter)) {
+                if now < last_action.saturating_add(self.config().vote_change_cooldown) {
+                    return Err(Error::VoteCooldown);
+                }
+            }
+            Ok(())
+        }
+
+        /// Calculates the consensus score for an event based on weighted votes
+        /// 
+        /// # Arguments
+        /// * `event` - Mutable reference to the event to calculate score for
+        /// 
+        /// Formula: (support_weight * 100) / total_weight
+        fn calculate_consensus_score(&self, event: &mut HistoricalEvent) {
+            let total_votes = event.support_votes.saturating_add(event.challenge_votes);
+            
+            if total_votes == 0 {
+                event.consensus_score = 0;
+            } else {
+                // Calculate percentage using integer arithmetic: (support_votes * 100) / total_votes
+                let numerator = event.support_votes.saturating_mul(100);
+                let score = numerator.checked_div(total_votes).unwrap_or(0);
+                event.consensus_score = score.min(100) as u8;
+            }
+        }
+
+        /// Checks if an event should move between timelines based on consensus score
+        /// 
+        /// # Arguments
+        /// * `event` - Mutable reference to the event to check
+        /// 
+        /// Timeline movement rules (default thresholds in parentheses):
+        /// - Fewer than `min_quorum` voters: Pending timeline
+        /// - Canonical with score >= `canonical_exit_threshold` (70): Stay Canonical
+        /// - Alternative with score <= `alternative_exit_threshold` (30): Stay Alternative
+        /// - Score >= `canonical_threshold` (75): Move to Canonical timeline
+        /// - Score <= `alternative_threshold` (25): Move to Alternative timeline
+        /// - Score in between (26-74): Remain in Disputed timeline
+        ///
+        /// The gap between entry and exit thresholds keeps events hovering
+        /// around a cutoff from flapping between timelines on every vote.
+        fn check_timeline_movement(&mut self, event: &mut HistoricalEvent) {
+            let config = self.config();
+            let old_timeline = event.timeline;
+            let new_timeline = if event.voter_count < config.min_quorum {
+                Timeline::Pending
+            } else if old_timeline == Timeline::Canonical
+                && event.consensus_score >= config.canonical_exit_threshold
+            {
+                Timeline::Canonical
+            } else if old_timeline == Timeline::Alternative
+                && event.consensus_score <= config.alternative_exit_threshold
+            {
+                Timeline::Alternative
+            } else if event.consensus_score >= config.canonical_threshold {
+                Timeline::Canonical
+            } else if event.consensus_score <= config.alternative_threshold {
+                Timeline::Alternative
+            } else {
+                Timeline::Disputed
+            };
+
+            // Only update if timeline changed
+            if old_timeline != new_timeline {
+                // Hidden events are kept out of the timeline index until restored
+                if !event.hidden {
+                    // Remove from old timeline
+                    if let Some(mut old_events) = self.timeline_events.get(old_timeline) {
+                        old_events.retain(|&id| id != event.id);
+                        self.timeline_events.insert(old_timeline, &old_events);
+                    }
+
+                    // Add to new timeline
+                    let mut new_events = self.timeline_events.get(new_timeline).unwrap_or_default();
+                    new_events.push(event.id);
+                    self.timeline_events.insert(new_timeline, &new_events);
+                }
+
+                // Update event's timeline
+                event.timeline = new_timeline;
+
+                // Append to the event's transition log, one transition per cell
+                let index = self.timeline_history_count.get(event.id).unwrap_or(0);
+                self.timeline_history.insert((event.id, index), &TimelineTransition {
+                    from: old_timeline,
+                    to: new_timeline,
+                    block_number: self.env().block_number(),
+                    timestamp: self.env().block_timestamp(),
+                    consensus_score: event.consensus_score,
+                });
+                self.timeline_history_count.insert(event.id, &index.saturating_add(1));
+
+                self.env().emit_event(TimelineChanged {
+                    event_id: event.id,
+                    old_timeline,
+                    new_timeline,
+                    consensus_score: event.consensus_score,
+                });
+            }
+        }
+
+        /// Retrieves a historical event by its ID
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event to retrieve
+        /// 
+        /// # Returns
+        /// * `Some(HistoricalEvent)` if the event exists
+        /// * `None` if the event doesn't exist
+        #[ink(message)]
+        pub fn get_event(&self, event_id: u64) -> Option<HistoricalEvent> {
+            self.events.get(event_id)
+        }
+
+        /// Retrieves a page of an event's timeline transitions, oldest first
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event
+        /// * `cursor` - Position in the transition log to start from (0 for the first transition)
+        /// * `limit` - Maximum number of transitions to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `Vec<TimelineTransition>` - The transitions (empty if the event never moved or doesn't exist);
+        ///   continue with `cursor + returned count` until empty
+        #[ink(message)]
+        pub fn get_timeline_history(&self, event_id: u64, cursor: u32, limit: u32) -> Vec<TimelineTransition> {
+            let count = self.timeline_history_count.get(event_id).unwrap_or(0);
+            let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (cursor..end)
+                .filter_map(|index| self.timeline_history.get((event_id, index)))
+                .collect()
+        }
+
+        /// Retrieves a page of an event's revisions, starting with the submitted content
+        /// 
+        /// A page stops early once its encoded size would exceed `MAX_PAGE_BYTES`.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event
+        /// * `from_revision` - Revision number to start from (0 for the submitted content)
+        /// * `limit` - Maximum number of revisions to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `Vec<EventRevision>` - The revisions in order (empty if the event doesn't exist);
+        ///   continue after the last returned revision number
+        #[ink(message)]
+        pub fn get_event_revisions(&self, event_id: u64, from_revision: u32, limit: u32) -> Vec<EventRevision> {
+            let Some(event) = self.events.get(event_id) else {
+                return Vec::new();
+            };
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            let mut revisions: Vec<EventRevision> = Vec::new();
+            let mut page_bytes = 0usize;
+            for number in from_revision..=event.revision {
+                if revisions.len() >= limit {
+                    break;
+                }
+                let Some(revision) = self.revision_snapshot(&event, number) else {
+                    continue;
+                };
+                let size = scale::Encode::encoded_size(&revision);
+                if !revisions.is_empty() && page_bytes.saturating_add(size) > MAX_PAGE_BYTES {
+                    break;
+                }
+                page_bytes = page_bytes.saturating_add(size);
+                revisions.push(revision);
+            }
+            revisions
+        }
+
+        /// Looks up one revision of an event; revision 0 of an unrevised event is its
+        /// current content
+        fn revision_snapshot(&self, event: &HistoricalEvent, revision: u32) -> Option<EventRevision> {
+            if event.revision == 0 {
+                return (revision == 0).then(|| Self::original_revision(event));
+            }
+            self.event_revisions.get((event.id, revision))
+        }
+
+        /// Retrieves an event with the content it had at a given revision
+        /// 
+        /// Votes, timeline and other state are always the current ones.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event
+        /// * `revision` - The revision number (0 for the content as submitted)
+        /// 
+        /// # Returns
+        /// * `Option<HistoricalEvent>` - None if the event or revision doesn't exist
+        #[ink(message)]
+        pub fn get_event_at_revision(&self, event_id: u64, revision: u32) -> Option<HistoricalEvent> {
+            let mut event = self.events.get(event_id)?;
+            if revision == event.revision {
+                return Some(event);
+            }
+            let snapshot = self.event_revisions.get((event_id, revision))?;
+            event.title = snapshot.title;
+            event.description = snapshot.description;
+            event.evidence_sources = snapshot.evidence_sources;
+            event.tags = snapshot.tags;
+            event.revision = snapshot.revision;
+            Some(event)
+        }
+
+        /// Retrieves an amendment by ID
+        #[ink(message)]
+        pub fn get_amendment(&self, amendment_id: u64) -> Option<Amendment> {
+            self.amendments.get(amendment_id)
+        }
+
+        /// Retrieves a page of the amendments proposed for an event, oldest first
+        /// 
+        /// A page stops early once its encoded size would exceed `MAX_PAGE_BYTES`.
+        /// 
+        /// # Arguments
+        /// * `event_id` - The unique ID of the event
+        /// * `cursor` - Position in the event's amendment list to start from (0 for the first)
+        /// * `limit` - Maximum number of amendments to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `Vec<Amendment>` - Continue with `cursor + returned count` until empty
+        #[ink(message)]
+        pub fn get_event_amendments(&self, event_id: u64, cursor: u32, limit: u32) -> Vec<Amendment> {
+            let count = self.event_amendment_count.get(event_id).unwrap_or(0);
+            let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            let mut amendments: Vec<Amendment> = Vec::new();
+            let mut page_bytes = 0usize;
+            for index in cursor..end {
+                let Some(amendment) = self
+                    .event_amendments
+                    .get((event_id, index))
+                    .and_then(|amendment_id| self.amendments.get(amendment_id))
+                else {
+                    continue;
+                };
+                let size = scale::Encode::encoded_size(&amendment);
+                if !amendments.is_empty() && page_bytes.saturating_add(size) > MAX_PAGE_BYTES {
+                    break;
+                }
+                page_bytes = page_bytes.saturating_add(size);
+                amendments.push(amendment);
+            }
+            amendments
+        }
+
+        /// Returns how an account voted on an amendment, if it did
+        #[ink(message)]
+        pub fn get_amendment_vote(&self, amendment_id: u64, voter: AccountId) -> Option<AmendmentVote> {
+            self.amendment_votes.get((amendment_id, voter))
+        }
+
+        /// Checks whether a vote was cast before the event's latest substantive revision
+        /// 
+        /// # Returns
+        /// * `bool` - false if the vote is current or doesn't exist
+        #[ink(message)]
+        pub fn is_vote_outdated(&self, event_id: u64, voter: AccountId) -> bool {
+            match (self.events.get(event_id), self.votes.get((event_id, voter))) {
+                (Some(event), Some(vote)) => vote.revision < event.substantive_revision,
+                _ => false,
+            }
+        }
+
+        /// Retrieves all events in a specific timeline
+        /// 
+        /// # Arguments
+        /// * `timeline` - The timeline to retrieve events from (Canonical, Disputed, or Alternative)
+        /// 
+        /// # Returns
+        /// * `Vec<HistoricalEvent>` - Vector of all events in the specified timeline
+        #[ink(message)]
+        pub fn get_events_by_timeline(&self, timeline: Timeline) -> Vec<HistoricalEvent> {
+            // Retrieve event IDs from timeline_events mapping
+            let event_ids = self.timeline_events.get(timeline).unwrap_or_default();
+            
+            // Fetch full event data for each ID
+            let mut events = Vec::new();
+            for event_id in event_ids.iter() {
+                if let Some(event) = self.events.get(*event_id) {
+                    events.push(event);
+                }
+            }
+            
+            events
+        }
+
+        /// Retrieves all events submitted by a specific user
+        /// 
+        /// # Arguments
+        /// * `user` - The account address of the user
+        /// 
+        /// # Returns
+        /// * `Vec<HistoricalEvent>` - Vector of all events submitted by the user
+        #[ink(message)]
+        pub fn get_user_events(&self, user: AccountId) -> Vec<HistoricalEvent> {
+            // Retrieve event IDs from user_events mapping
+            let event_ids = self.user_events.get(user).unwrap_or_default();
+            
+            // Fetch full event data for each ID
+            let mut events = Vec::new();
+            for event_id in event_ids.iter() {
+                if let Some(event) = self.events.get(*event_id) {
+                    events.push(event);
+                }
+            }
+            
+            events
+        }
+
+        /// Retrieves all events that contain a specific tag
+        /// 
+        /// # Arguments
+        /// * `tag` - The tag to filter events by
+        /// 
+        /// # Returns
+        /// * `Vec<HistoricalEvent>` - Vector of all events containing the specified tag
+        #[ink(message)]
+        pub fn get_events_by_tag(&self, tag: String) -> Vec<HistoricalEvent> {
+            // Retrieve event IDs from the tag index
+            let event_ids = self.tag_event_ids(&tag);
+            
+            // Fetch full event data for each ID
+            let mut matching_events = Vec::new();
+            for event_id in event_ids.iter() {
+                if let Some(event) = self.events.get(*event_id) {
+                    matching_events.push(event);
+                }
+            }
+            
+            matching_events
+        }
+
+        /// Lists all known tags with the number of events carrying each
+        /// 
+        /// # Returns
+        /// * `Vec<TagInfo>` - Every tag in use, in the order it was first seen
+        #[ink(message)]
+        pub fn list_tags(&self) -> Vec<TagInfo> {
+            let mut tags = Vec::new();
+            for index in 0..self.known_tag_count.get_or_default() {
+                if let Some(tag) = self.known_tags.get(index) {
+                    let event_count = self.tag_event_count.get(&tag).unwrap_or(0);
+                    if event_count > 0 {
+                        tags.push(TagInfo { tag, event_count });
+                    }
+                }
+            }
+            tags
+        }
+
+        /// Retrieves a page of events in a specific timeline
+        /// 
+        /// # Arguments
+        /// * `timeline` - The timeline to retrieve events from
+        /// * `cursor` - Position to start from (0 for the first page)
+        /// * `limit` - Maximum number of events to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `EventPage` - The requested page, the next cursor and the total count
+        #[ink(message)]
+        pub fn get_events_by_timeline_paged(&self, timeline: Timeline, cursor: u64, limit: u32) -> EventPage {
+            let event_ids = self.timeline_events.get(timeline).unwrap_or_default();
+            self.paginate(&event_ids, cursor, limit)
+        }
+
+        /// Retrieves a page of events submitted by a specific user
+        /// 
+        /// # Arguments
+        /// * `user` - The account address of the user
+        /// * `cursor` - Position to start from (0 for the first page)
+        /// * `limit` - Maximum number of events to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `EventPage` - The requested page, the next cursor and the total count
+        #[ink(message)]
+        pub fn get_user_events_paged(&self, user: AccountId, cursor: u64, limit: u32) -> EventPage {
+            let event_ids = self.user_events.get(user).unwrap_or_default();
+            self.paginate(&event_ids, cursor, limit)
+        }
+
+        /// Retrieves a page of events that contain a specific tag
+        /// 
+        /// Removing an event from a tag moves the tag's last event into its position,
+        /// so restart from cursor 0 if `total` changes between pages.
+        /// 
+        /// # Arguments
+        /// * `tag` - The tag to filter events by
+        /// * `cursor` - Position to start from (0 for the first page)
+        /// * `limit` - Maximum number of events to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `EventPage` - The requested page, the next cursor and the total count
+        #[ink(message)]
+        pub fn get_events_by_tag_paged(&self, tag: String, cursor: u64, limit: u32) -> EventPage {
+            let total = u64::from(self.tag_event_count.get(&tag).unwrap_or(0));
+            self.paginate_by(
+                total,
+                |position| u32::try_from(position).ok().and_then(|position| self.tag_events.get((&tag, position))),
+                cursor,
+                limit,
+                |_| true,
+            )
+        }
+
+        /// Retrieves the IDs of all events matching a filter
+        /// 
+        /// Historical dates are not indexed, so a date range is checked against every
+        /// event the tag and timeline criteria match; use `query_events` to page through
+        /// broad matches instead.
+        /// 
+        /// # Arguments
+        /// * `filter` - Tags (with any/all semantics), timeline and date range to match
+        /// 
+        /// # Returns
+        /// * `Ok(Vec<u64>)` - Matching event IDs in ascending order
+        /// * `Err(Error::QueryTooBroad)` if the filter has neither tags nor a timeline
+        #[ink(message)]
+        pub fn query_event_ids(&self, filter: EventFilter) -> Result<Vec<u64>, Error> {
+            let mut event_ids = self.indexed_event_ids(&filter)?;
+            if let Some((from, to)) = filter.date_range() {
+                event_ids.retain(|id| {
+                    self.events
+                        .get(*id)
+                        .map(|event| event.date >= from && event.date <= to)
+                        .unwrap_or(false)
+                });
+            }
+            Ok(event_ids)
+        }
+
+        /// Retrieves a page of events matching a filter
+        /// 
+        /// Paging runs over the events matched by the tag and timeline criteria, and the
+        /// date range is applied to each page as it is read, so a page can hold fewer
+        /// than `limit` events (or none) while `next_cursor` is still set. `total` counts
+        /// the indexed matches before the date range is applied.
+        /// 
+        /// # Arguments
+        /// * `filter` - Tags (with any/all semantics), timeline and date range to match
+        /// * `cursor` - Position to start from (0 for the first page)
+        /// * `limit` - Maximum number of indexed matches to read (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// # Returns
+        /// * `Ok(EventPage)` - The requested page, the next cursor and the total count
+        /// * `Err(Error::QueryTooBroad)` if the filter has neither tags nor a timeline
+        #[ink(message)]
+        pub fn query_events(&self, filter: EventFilter, cursor: u64, limit: u32) -> Result<EventPage, Error> {
+            let event_ids = self.indexed_event_ids(&filter)?;
+            let (from, to) = filter.date_range().unwrap_or((u64::MIN, u64::MAX));
+            Ok(self.paginate_matching(&event_ids, cursor, limit, |event| {
+                event.date >= from && event.date <= to
+            }))
+        }
+
+        /// Exports stored events in ID order, hidden ones included
+        /// 
+        /// # Arguments
+        /// * `from_id` - First event ID to export (IDs start at 1)
+        /// * `limit` - Maximum number of events to return (capped at `MAX_PAGE_SIZE`,
+        ///   and by `MAX_PAGE_BYTES` of encoded events)
+        /// 
+        /// # Returns
+        /// * `Vec<HistoricalEvent>` - Continue from the last returned ID + 1 until empty
+        #[ink(message)]
+        pub fn export_events(&self, from_id: u64, limit: u32) -> Vec<HistoricalEvent> {
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            let mut events: Vec<HistoricalEvent> = Vec::new();
+            let mut page_bytes = 0usize;
+            for event_id in from_id.max(1)..=self.event_count {
+                if events.len() >= limit {
+                    break;
+                }
+                if let Some(event) = self.events.get(event_id) {
+                    let size = scale::Encode::encoded_size(&event);
+                    if !events.is_empty() && page_bytes.saturating_add(size) > MAX_PAGE_BYTES {
+                        break;
+                    }
+                    page_bytes = page_bytes.saturating_add(size);
+                    events.push(event);
+                }
+            }
+            events
+        }
+
+        /// Exports the current votes on an event
+        /// 
+        /// # Arguments
+        /// * `event_id` - The event whose votes to export
+        /// * `cursor` - Position in the event's voter list to start from (0 for the first page)
+        /// * `limit` - Maximum number of votes to return (capped at `MAX_PAGE_SIZE`)
+        /// 
+        /// Retracting a vote moves the last voter into its position, so export from a
+        /// paused contract to get a consistent snapshot.
+        /// 
+        /// # Returns
+        /// * `Vec<Vote>` - Continue with `cursor + returned count` until empty
+        #[ink(message)]
+        pub fn export_votes(&self, event_id: u64, cursor: u32, limit: u32) -> Vec<Vote> {
+            let count = self.event_voter_count.get(event_id).unwrap_or(0);
+            let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (cursor..end)
+                .filter_map(|index| self.event_voters.get((event_id, index)))
+                .filter_map(|voter| self.votes.get((event_id, voter)))
+                .collect()
+        }
+
+        /// Imports events exported from another deployment (owner or admins only)
+        /// 
+        /// IDs, submitters, timestamps and tallies are kept as exported; the timeline,
+        /// user and tag indexes are rebuilt (hidden events stay out of them). Deposits and
+        /// stakes are not carried over.
+        /// 
+        /// # Arguments
+        /// * `events` - Up to `MAX_PAGE_SIZE` events, as returned by `export_events`
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is neither the owner nor an admin
+        /// * `Err(Error::BatchTooLarge)` if more than `MAX_PAGE_SIZE` events are given
+        /// * `Err(Error::InvalidEventId)` if an event has ID 0
+        /// * `Err(Error::EventAlreadyExists)` if an event ID is already taken
+        #[ink(message)]
+        pub fn import_events(&mut self, events: Vec<HistoricalEvent>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if events.len() > MAX_PAGE_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+            for (i, event) in events.iter().enumerate() {
+                if event.id == 0 {
+                    return Err(Error::InvalidEventId);
+                }
+                if self.events.contains(event.id) || events[..i].iter().any(|e| e.id == event.id) {
+                    return Err(Error::EventAlreadyExists);
+                }
+            }
+
+            for event in events.iter() {
+                self.events.insert(event.id, event);
+                if !event.hidden {
+                    self.index_event(event);
+                }
+
+                // Keep new submissions from reusing imported IDs
+                self.event_count = self.event_count.max(event.id);
+            }
+
+            Ok(())
+        }
+
+        /// Imports votes exported from another deployment (owner or admins only)
+        /// 
+        /// Import the events first; their tallies already account for these votes,
+        /// so only the vote records and voter lists are restored. Stakes stay with the
+        /// old deployment: imported votes keep their weight but hold no stake, so
+        /// retracting one later pays nothing out.
+        /// 
+        /// # Arguments
+        /// * `votes` - Up to `MAX_PAGE_SIZE` votes, as returned by `export_votes`
+        /// 
+        /// # Returns
+        /// * `Err(Error::Unauthorized)` if the caller is neither the owner nor an admin
+        /// * `Err(Error::BatchTooLarge)` if more than `MAX_PAGE_SIZE` votes are given
+        /// * `Err(Error::EventNotFound)` if a vote refers to an event that was not imported
+        /// * `Err(Error::AlreadyVoted)` if the voter already has a vote on that event
+        #[ink(message)]
+        pub fn import_votes(&mut self, votes: Vec<Vote>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if votes.len() > MAX_PAGE_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+            for (i, vote) in votes.iter().enumerate() {
+                if !self.events.contains(vote.event_id) {
+                    return Err(Error::EventNotFound);
+                }
+                if self.votes.contains((vote.event_id, vote.voter))
+                    || votes[..i]
+                        .iter()
+                        .any(|v| v.event_id == vote.event_id && v.voter == vote.voter)
+                {
+                    return Err(Error::AlreadyVoted);
+                }
+            }
+
+            for vote in votes.iter() {
+                let vote = Vote {
+                    stake: 0,
+                    locked_until: vote.timestamp,
+                    ..vote.clone()
+                };
+                self.votes.insert((vote.event_id, vote.voter), &vote);
+                self.last_vote_action.insert((vote.event_id, vote.voter), &vote.timestamp);
+                self.add_event_voter(vote.event_id, vote.voter);
+            }
+
+            Ok(())
+        }
+
+        /// Looks up the IDs matching a filter's tag and timeline criteria, in ascending order
+        fn indexed_event_ids(&self, filter: &EventFilter) -> Result<Vec<u64>, Error> {
+            // Start from the narrowest index available
+            let mut event_ids = if !filter.tags.is_empty() {
+                self.event_ids_by_tags(&filter.tags, filter.match_mode)
+            } else if let Some(timeline) = filter.timeline {
+                let mut ids = self.timeline_events.get(timeline).unwrap_or_default();
+                ids.sort_unstable();
+                ids
+            } else {
+                return Err(Error::QueryTooBroad);
+            };
+
+            // Intersect with the timeline index
+            if let Some(timeline) = filter.timeline {
+                if !filter.tags.is_empty() {
+                    let timeline_ids = self.timeline_events.get(timeline).unwrap_or_default();
+                    event_ids.retain(|id| timeline_ids.contains(id));
+                }
+            }
+
+            Ok(event_ids)
+        }
+
+        /// Combines the tag index entries for several tags into a sorted ID list
+        fn event_ids_by_tags(&self, tags: &[String], match_mode: TagMatch) -> Vec<u64> {
+            let mut event_ids: Vec<u64> = match match_mode {
+                TagMatch::Any => tags.iter().flat_map(|tag| self.tag_event_ids(tag)).collect(),
+                TagMatch::All => {
+                    // Read the shortest list and look the others up by event
+                    let Some(shortest) = tags
+                        .iter()
+                        .min_by_key(|tag| self.tag_event_count.get(*tag).unwrap_or(0))
+                    else {
+                        return Vec::new();
+                    };
+                    let mut ids = self.tag_event_ids(shortest);
+                    for other in tags.iter().filter(|tag| *tag != shortest) {
+                        ids.retain(|id| self.tag_event_index.contains((other, *id)));
+                    }
+                    ids
+                }
+            };
+
+            event_ids.sort_unstable();
+            event_ids.dedup();
+            event_ids
+        }
+
+        /// Loads the events for one page of an ID list
+        /// 
+        /// The page ends early once its events would exceed `MAX_PAGE_BYTES`; it always
+        /// holds at least one event so the cursor keeps moving.
+        fn paginate(&self, event_ids: &[u64], cursor: u64, limit: u32) -> EventPage {
+            self.paginate_matching(event_ids, cursor, limit, |_| true)
+        }
+
+        /// Like `paginate`, but leaves out events rejected by `keep`, so a page can hold
+        /// fewer than `limit` events
+        fn paginate_matching<F>(&self, event_ids: &[u64], cursor: u64, limit: u32, keep: F) -> EventPage
+        where
+            F: Fn(&HistoricalEvent) -> bool,
+        {
+            self.paginate_by(
+                event_ids.len() as u64,
+                |position| usize::try_from(position).ok().and_then(|position| event_ids.get(position)).copied(),
+                cursor,
+                limit,
+                keep,
+            )
+        }
+
+        /// Like `paginate_matching`, but reads the ID at each list position through
+        /// `id_at`, so lists stored one entry per cell are only read for the page
+        fn paginate_by<I, F>(&self, total: u64, id_at: I, cursor: u64, limit: u32, keep: F) -> EventPage
+        where
+            I: Fn(u64) -> Option<u64>,
+            F: Fn(&HistoricalEvent) -> bool,
+        {
+            let start = cursor.min(total);
+            let last = start.saturating_add(u64::from(limit.min(MAX_PAGE_SIZE))).min(total);
+
+            let mut events: Vec<HistoricalEvent> = Vec::new();
+            let mut page_bytes = 0usize;
+            let mut end = start;
+            while end < last {
+                if let Some(event) = id_at(end).and_then(|event_id| self.events.get(event_id)) {
+                    if keep(&event) {
+                        let size = scale::Encode::encoded_size(&event);
+                        if !events.is_empty() && page_bytes.saturating_add(size) > MAX_PAGE_BYTES {
+                            break;
+                        }
+                        page_bytes = page_bytes.saturating_add(size);
+                        events.push(event);
+                    }
+                }
+                end = end.saturating_add(1);
+            }
+
+            let next_cursor = if end < total && end > start {
+                Some(end)
+            } else {
+                None
+            };
+
+            EventPage {
+                events,
+                next_cursor,
+                total,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Event = <HistoryProtocol as ::ink::reflect::ContractEventBase>::Type;
+
+        /// Builds a free-text archive citation
+        fn evidence(citation: &str) -> Evidence {
+            Evidence {
+                kind: EvidenceKind::Archive,
+                citation: String::from(citation),
+                content_hash: None,
+                archived_copy: None,
+            }
+        }
+
+        /// Builds a URL citation
+        fn url(citation: &str) -> Evidence {
+            Evidence {
+                kind: EvidenceKind::Url,
+                citation: String::from(citation),
+                ..evidence(citation)
+            }
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let contract = HistoryProtocol::new();
+            assert_eq!(contract.event_count, 0);
+        }
+
+        #[ink::test]
+        fn submit_event_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            let title = String::from("First Moon Landing");
+            let date = 19690720u64;
+            let description = String::from("Apollo 11 successfully landed on the moon");
+            let evidence = vec![
+                url("https://nasa.gov/apollo11"),
+                url("https://archive.org/moon-landing")
+            ];
+            let tags = vec![
+                String::from("Space"),
+                String::from("Science")
+            ];
+
+            let result = contract.submit_event(
+                title.clone(),
+                date,
+                description.clone(),
+                evidence.clone(),
+                tags.clone()
+            );
+
+            assert!(result.is_ok());
+            let event_id = result.unwrap();
+            assert_eq!(event_id, 1);
+            assert_eq!(contract.event_count, 1);
+
+            // Verify event was stored correctly
+            let stored_event = contract.events.get(event_id);
+            assert!(stored_event.is_some());
+            
+            let event = stored_event.unwrap();
+            assert_eq!(event.id, event_id);
+            assert_eq!(event.title, title);
+            assert_eq!(event.date, date);
+            assert_eq!(event.description, description);
+            assert_eq!(event.evidence_sources, evidence);
+            assert_eq!(event.tags, tags);
+            assert_eq!(event.timeline, Timeline::Pending);
+            assert_eq!(event.consensus_score, 0);
+            assert_eq!(event.support_votes, 0);
+            assert_eq!(event.challenge_votes, 0);
+        }
+
+        #[ink::test]
+        fn submit_event_fails_with_empty_title() {
+            let mut contract = HistoryProtocol::new();
+            
+            let result = contract.submit_event(
+                String::from(""),
+                19690720u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+
+            assert_eq!(result, Err(Error::EmptyTitle));
+            assert_eq!(contract.event_count, 0);
+        }
+
+        #[ink::test]
+        fn submit_event_fails_with_empty_description() {
+            let mut contract = HistoryProtocol::new();
+            
+            let result = contract.submit_event(
+                String::from("Title"),
+                19690720u64,
+                String::from(""),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+
+            assert_eq!(result, Err(Error::EmptyDescription));
+            assert_eq!(contract.event_count, 0);
+        }
+
+        #[ink::test]
+        fn submit_event_fails_with_no_evidence() {
+            let mut contract = HistoryProtocol::new();
+            
+            let result = contract.submit_event(
+                String::from("Title"),
+                19690720u64,
+                String::from("Description"),
+                vec![],
+                vec![String::from("Science")]
+            );
+
+            assert_eq!(result, Err(Error::NoEvidence));
+            assert_eq!(contract.event_count, 0);
+        }
+
+        #[ink::test]
+        fn event_id_generation_is_unique() {
+            let mut contract = HistoryProtocol::new();
+            
+            let title = String::from("Event");
+            let date = 1000u64;
+            let description = String::from("Description");
+            let evidence = vec![evidence("evidence")];
+            let tags = vec![String::from("Science")];
+
+            // Submit first event
+            let result1 = contract.submit_event(
+                title.clone(),
+                date,
+                description.clone(),
+                evidence.clone(),
+                tags.clone()
+            );
+            assert!(result1.is_ok());
+            let event_id1 = result1.unwrap();
+
+            // Submit second event
+            let result2 = contract.submit_event(
+                title.clone(),
+                date,
+                description.clone(),
+                evidence.clone(),
+                tags.clone()
+            );
+            assert!(result2.is_ok());
+            let event_id2 = result2.unwrap();
+
+            // Verify IDs are unique
+            assert_eq!(event_id1, 1);
+            assert_eq!(event_id2, 2);
+            assert_ne!(event_id1, event_id2);
+            assert_eq!(contract.event_count, 2);
+        }
+
+        #[ink::test]
+        fn vote_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event first
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Vote on the event
+            let result = contract.vote(event_id, true);
+            assert!(result.is_ok());
+
+            // Verify vote was recorded
+            let caller = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice;
+            assert!(contract.has_voted(event_id, caller));
+
+            // Verify vote counts updated
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.support_votes, 1);
+            assert_eq!(event.challenge_votes, 0);
+            assert_eq!(event.consensus_score, 100);
+        }
+
+        #[ink::test]
+        fn vote_prevents_duplicate_voting() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // First vote should succeed
+            let result1 = contract.vote(event_id, true);
+            assert!(result1.is_ok());
+
+            // Second vote from same user should fail
+            let result2 = contract.vote(event_id, false);
+            assert_eq!(result2, Err(Error::AlreadyVoted));
+
+            // Verify only one vote was counted
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.support_votes, 1);
+            assert_eq!(event.challenge_votes, 0);
+        }
+
+        #[ink::test]
+        fn vote_fails_on_nonexistent_event() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Try to vote on non-existent event
+            let result = contract.vote(999, true);
+            assert_eq!(result, Err(Error::EventNotFound));
+        }
+
+        #[ink::test]
+        fn has_voted_returns_false_for_new_voter() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let caller = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice;
+            assert!(!contract.has_voted(event_id, caller));
+        }
+
+        #[ink::test]
+        fn consensus_score_calculation_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Vote with support
+            contract.vote(event_id, true).unwrap();
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.consensus_score, 100); // 1/1 = 100%
+
+            // Change caller and vote with challenge
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob
+            );
+            contract.vote(event_id, false).unwrap();
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.consensus_score, 50); // 1/2 = 50%
+        }
+
+        #[ink::test]
+        fn timeline_movement_to_canonical_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Initial timeline should be Pending
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.timeline, Timeline::Pending);
+
+            // Add 3 support votes to reach 75%+ consensus
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(event_id, true).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, true).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, true).unwrap();
+
+            // Should move to Canonical (3/3 = 100%)
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.timeline, Timeline::Canonical);
+            assert_eq!(event.consensus_score, 100);
+        }
+
+        #[ink::test]
+        fn timeline_movement_to_alternative_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Add 3 challenge votes to reach 25% or less consensus
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(event_id, false).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, false).unwrap();
+
+            // Should move to Alternative (0/3 = 0%)
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.timeline, Timeline::Alternative);
+            assert_eq!(event.consensus_score, 0);
+        }
+
+        #[ink::test]
+        fn timeline_stays_disputed_for_mixed_votes() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Add mixed votes to keep in Disputed range (26-74%)
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(event_id, true).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            contract.vote(event_id, false).unwrap();
+
+            // Should stay in Disputed (2/4 = 50%)
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.timeline, Timeline::Disputed);
+            assert_eq!(event.consensus_score, 50);
+        }
+
+        #[ink::test]
+        fn get_event_returns_existing_event() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let title = String::from("Test Event");
+            let date = 1000u64;
+            let description = String::from("Test Description");
+            let evidence = vec![evidence("evidence1")];
+            let tags = vec![String::from("Science")];
+            
+            let event_id = contract.submit_event(
+                title.clone(),
+                date,
+                description.clone(),
+                evidence.clone(),
+                tags.clone()
+            ).unwrap();
+
+            // Retrieve the event
+            let retrieved_event = contract.get_event(event_id);
+            assert!(retrieved_event.is_some());
+            
+            let event = retrieved_event.unwrap();
+            assert_eq!(event.id, event_id);
+            assert_eq!(event.title, title);
+            assert_eq!(event.date, date);
+            assert_eq!(event.description, description);
+            assert_eq!(event.evidence_sources, evidence);
+            assert_eq!(event.tags, tags);
+        }
+
+        #[ink::test]
+        fn get_event_returns_none_for_nonexistent_event() {
+            let contract = HistoryProtocol::new();
+            
+            // Try to retrieve non-existent event
+            let retrieved_event = contract.get_event(999);
+            assert!(retrieved_event.is_none());
+        }
+
+        #[ink::test]
+        fn get_events_by_timeline_returns_pending_events() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit multiple events (all start in Pending timeline)
+            let event_id1 = contract.submit_event(
+                String::from("Event 1"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science")]
+            ).unwrap();
+            
+            let event_id2 = contract.submit_event(
+                String::from("Event 2"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Technology")]
+            ).unwrap();
+
+            // Retrieve events from Pending timeline
+            let pending_events = contract.get_events_by_timeline(Timeline::Pending);
+            assert_eq!(pending_events.len(), 2);
+            
+            // Verify both events are present
+            assert!(pending_events.iter().any(|e| e.id == event_id1));
+            assert!(pending_events.iter().any(|e| e.id == event_id2));
+        }
+
+        #[ink::test]
+        fn get_events_by_timeline_returns_canonical_events() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Vote to move to Canonical timeline (3 support votes = 100%)
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(event_id, true).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, true).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, true).unwrap();
+
+            // Retrieve events from Canonical timeline
+            let canonical_events = contract.get_events_by_timeline(Timeline::Canonical);
+            assert_eq!(canonical_events.len(), 1);
+            assert_eq!(canonical_events[0].id, event_id);
+            assert_eq!(canonical_events[0].timeline, Timeline::Canonical);
+
+            // Pending timeline should now be empty
+            let pending_events = contract.get_events_by_timeline(Timeline::Pending);
+            assert_eq!(pending_events.len(), 0);
+        }
+
+        #[ink::test]
+        fn get_events_by_timeline_returns_alternative_events() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit an event
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Vote to move to Alternative timeline (3 challenge votes = 0%)
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(event_id, false).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+            
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, false).unwrap();
+
+            // Retrieve events from Alternative timeline
+            let alternative_events = contract.get_events_by_timeline(Timeline::Alternative);
+            assert_eq!(alternative_events.len(), 1);
+            assert_eq!(alternative_events[0].id, event_id);
+            assert_eq!(alternative_events[0].timeline, Timeline::Alternative);
+
+            // Pending timeline should now be empty
+            let pending_events = contract.get_events_by_timeline(Timeline::Pending);
+            assert_eq!(pending_events.len(), 0);
+        }
+
+        #[ink::test]
+        fn get_events_by_timeline_returns_empty_for_empty_timeline() {
+            let contract = HistoryProtocol::new();
+            
+            // All timelines should be empty initially
+            let canonical_events = contract.get_events_by_timeline(Timeline::Canonical);
+            assert_eq!(canonical_events.len(), 0);
+            
+            let disputed_events = contract.get_events_by_timeline(Timeline::Disputed);
+            assert_eq!(disputed_events.len(), 0);
+            
+            let alternative_events = contract.get_events_by_timeline(Timeline::Alternative);
+            assert_eq!(alternative_events.len(), 0);
+
+            let pending_events = contract.get_events_by_timeline(Timeline::Pending);
+            assert_eq!(pending_events.len(), 0);
+        }
+
+        #[ink::test]
+        fn get_user_events_returns_user_submitted_events() {
+            let mut contract = HistoryProtocol::new();
+            
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            // Alice submits two events
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let event_id1 = contract.submit_event(
+                String::from("Alice Event 1"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science")]
+            ).unwrap();
+            
+            let event_id2 = contract.submit_event(
+                String::from("Alice Event 2"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Technology")]
+            ).unwrap();
+
+            // Bob submits one event
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let event_id3 = contract.submit_event(
+                String::from("Bob Event"),
+                3000u64,
+                String::from("Description 3"),
+                vec![evidence("evidence3")],
+                vec![String::from("Politics")]
+            ).unwrap();
+
+            // Retrieve Alice's events
+            let alice_events = contract.get_user_events(accounts.alice);
+            assert_eq!(alice_events.len(), 2);
+            assert!(alice_events.iter().any(|e| e.id == event_id1));
+            assert!(alice_events.iter().any(|e| e.id == event_id2));
+            assert!(!alice_events.iter().any(|e| e.id == event_id3));
+
+            // Retrieve Bob's events
+            let bob_events = contract.get_user_events(accounts.bob);
+            assert_eq!(bob_events.len(), 1);
+            assert_eq!(bob_events[0].id, event_id3);
+        }
+
+        #[ink::test]
+        fn get_user_events_returns_empty_for_user_with_no_events() {
+            let contract = HistoryProtocol::new();
+            
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            // Charlie has not submitted any events
+            let charlie_events = contract.get_user_events(accounts.charlie);
+            assert_eq!(charlie_events.len(), 0);
+        }
+
+        #[ink::test]
+        fn submit_event_with_tags_works() {
+            let mut contract = HistoryProtocol::new();
+            
+            let tags = vec![
+                String::from("Science"),
+                String::from("Technology"),
+                String::from("Space")
+            ];
+
+            let result = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                tags.clone()
+            );
+
+            assert!(result.is_ok());
+            let event_id = result.unwrap();
+            
+            let event = contract.events.get(event_id).unwrap();
+            assert_eq!(event.tags, tags);
+        }
+
+        #[ink::test]
+        fn submit_event_fails_with_empty_tags() {
+            let mut contract = HistoryProtocol::new();
+            
+            let result = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![]
+            );
+
+            assert_eq!(result, Err(Error::NoTags));
+        }
+
+        #[ink::test]
+        fn submit_event_fails_with_too_many_tags() {
+            let mut contract = HistoryProtocol::new();
+            
+            let tags = vec![
+                String::from("Tag1"),
+                String::from("Tag2"),
+                String::from("Tag3"),
+                String::from("Tag4"),
+                String::from("Tag5"),
+                String::from("Tag6")
+            ];
+
+            let result = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                tags
+            );
+
+            assert_eq!(result, Err(Error::TooManyTags));
+        }
+
+        #[ink::test]
+        fn get_events_by_tag_returns_matching_events() {
+            let mut contract = HistoryProtocol::new();
+            
+            // Submit events with different tags
+            let event_id1 = contract.submit_event(
+                String::from("Science Event"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science"), String::from("Technology")]
+            ).unwrap();
+            
+            let event_id2 = contract.submit_event(
+                String::from("Politics Event"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Politics")]
+            ).unwrap();
+            
+            let event_id3 = contract.submit_event(
+                String::from("Another Science Event"),
+                3000u64,
+                String::from("Description 3"),
+                vec![evidence("evidence3")],
+                vec![String::from("Science"), String::from("Space")]
+            ).unwrap();
+
+            // Query events by "Science" tag
+            let science_events = contract.get_events_by_tag(String::from("Science"));
+            assert_eq!(science_events.len(), 2);
+            assert!(science_events.iter().any(|e| e.id == event_id1));
+            assert!(science_events.iter().any(|e| e.id == event_id3));
+            assert!(!science_events.iter().any(|e| e.id == event_id2));
+
+            // Query events by "Politics" tag
+            let politics_events = contract.get_events_by_tag(String::from("Politics"));
+            assert_eq!(politics_events.len(), 1);
+            assert_eq!(politics_events[0].id, event_id2);
+        }
+
+        #[ink::test]
+        fn submit_event_emits_event_submitted() {
+            let mut contract = HistoryProtocol::new();
+
+            contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 1);
+
+            let decoded = match <Event as scale::Decode>::decode(&mut &emitted_events[0].data[..]) {
+                Ok(Event::EventSubmitted(event)) => event,
+                _ => panic!("expected EventSubmitted"),
+            };
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(decoded.event_id, 1);
+            assert_eq!(decoded.submitter, accounts.alice);
+            assert_eq!(decoded.timeline, Timeline::Pending);
+        }
+
+        #[ink::test]
+        fn vote_emits_vote_cast_and_timeline_changed() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                min_quorum: 1,
+                ..Default::default()
+            }).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // A single support vote moves the event to Canonical (1/1 = 100%)
+            contract.vote(event_id, true).unwrap();
+
+            // EventSubmitted, TimelineChanged, VoteCast
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+
+            let timeline_changed = match <Event as scale::Decode>::decode(&mut &emitted_events[1].data[..]) {
+                Ok(Event::TimelineChanged(event)) => event,
+                _ => panic!("expected TimelineChanged"),
+            };
+            assert_eq!(timeline_changed.event_id, event_id);
+            assert_eq!(timeline_changed.old_timeline, Timeline::Pending);
+            assert_eq!(timeline_changed.new_timeline, Timeline::Canonical);
+            assert_eq!(timeline_changed.consensus_score, 100);
+
+            let vote_cast = match <Event as scale::Decode>::decode(&mut &emitted_events[2].data[..]) {
+                Ok(Event::VoteCast(event)) => event,
+                _ => panic!("expected VoteCast"),
+            };
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(vote_cast.event_id, event_id);
+            assert_eq!(vote_cast.voter, accounts.alice);
+            assert!(vote_cast.support);
+            assert_eq!(vote_cast.consensus_score, 100);
+        }
+
+        #[ink::test]
+        fn get_events_by_timeline_paged_works() {
+            let mut contract = HistoryProtocol::new();
+
+            for i in 0..5u64 {
+                contract.submit_event(
+                    String::from("Event"),
+                    1000u64 + i,
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from("Science")]
+                ).unwrap();
+            }
+
+            // First page
+            let page = contract.get_events_by_timeline_paged(Timeline::Pending, 0, 2);
+            assert_eq!(page.total, 5);
+            assert_eq!(page.events.len(), 2);
+            assert_eq!(page.events[0].id, 1);
+            assert_eq!(page.events[1].id, 2);
+            assert_eq!(page.next_cursor, Some(2));
+
+            // Last page
+            let page = contract.get_events_by_timeline_paged(Timeline::Pending, 4, 2);
+            assert_eq!(page.events.len(), 1);
+            assert_eq!(page.events[0].id, 5);
+            assert_eq!(page.next_cursor, None);
+
+            // Cursor past the end
+            let page = contract.get_events_by_timeline_paged(Timeline::Pending, 10, 2);
+            assert_eq!(page.events.len(), 0);
+            assert_eq!(page.next_cursor, None);
+            assert_eq!(page.total, 5);
+        }
+
+        #[ink::test]
+        fn paged_queries_cap_limit_at_max_page_size() {
+            let mut contract = HistoryProtocol::new();
+
+            for _ in 0..(MAX_PAGE_SIZE + 1) {
+                contract.submit_event(
+                    String::from("Event"),
+                    1000u64,
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from("Science")]
+                ).unwrap();
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let page = contract.get_user_events_paged(accounts.alice, 0, u32::MAX);
+            assert_eq!(page.events.len(), MAX_PAGE_SIZE as usize);
+            assert_eq!(page.next_cursor, Some(MAX_PAGE_SIZE as u64));
+            assert_eq!(page.total, (MAX_PAGE_SIZE + 1) as u64);
+        }
+
+        #[ink::test]
+        fn get_events_by_tag_paged_works() {
+            let mut contract = HistoryProtocol::new();
+
+            contract.submit_event(
+                String::from("Science Event"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science")]
+            ).unwrap();
+            contract.submit_event(
+                String::from("Politics Event"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Politics")]
+            ).unwrap();
+            let event_id3 = contract.submit_event(
+                String::from("Another Science Event"),
+                3000u64,
+                String::from("Description 3"),
+                vec![evidence("evidence3")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let page = contract.get_events_by_tag_paged(String::from("Science"), 1, 10);
+            assert_eq!(page.total, 2);
+            assert_eq!(page.events.len(), 1);
+            assert_eq!(page.events[0].id, event_id3);
+            assert_eq!(page.next_cursor, None);
+        }
+
+        #[ink::test]
+        fn list_tags_returns_tag_counts() {
+            let mut contract = HistoryProtocol::new();
+
+            assert!(contract.list_tags().is_empty());
+
+            contract.submit_event(
+                String::from("Science Event"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science"), String::from("Technology")]
+            ).unwrap();
+            contract.submit_event(
+                String::from("Another Science Event"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let tags = contract.list_tags();
+            assert_eq!(tags, vec![
+                TagInfo { tag: String::from("Science"), event_count: 2 },
+                TagInfo { tag: String::from("Technology"), event_count: 1 },
+            ]);
+        }
+
+        #[ink::test]
+        fn unindex_event_tags_removes_event_from_tag() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Science Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science"), String::from("Space")]
+            ).unwrap();
+
+            contract.unindex_event_tags(event_id, &[String::from("Space")]);
+
+            assert_eq!(contract.get_events_by_tag(String::from("Space")).len(), 0);
+            assert_eq!(contract.get_events_by_tag(String::from("Science")).len(), 1);
+            assert_eq!(contract.list_tags(), vec![
+                TagInfo { tag: String::from("Science"), event_count: 1 },
+            ]);
+        }
+
+        #[ink::test]
+        fn unindexing_moves_last_tagged_event_into_freed_slot() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_ids: Vec<u64> = (0..4)
+                .map(|_| {
+                    contract.submit_event(
+                        String::from("Science Event"),
+                        1000u64,
+                        String::from("Description"),
+                        vec![evidence("evidence")],
+                        vec![String::from("Science")]
+                    ).unwrap()
+                })
+                .collect();
+
+            let science = [String::from("Science")];
+            contract.unindex_event_tags(event_ids[1], &science);
+            contract.unindex_event_tags(event_ids[3], &science);
+
+            let page = contract.get_events_by_tag_paged(String::from("Science"), 0, 10);
+            let ids: Vec<u64> = page.events.iter().map(|event| event.id).collect();
+            assert_eq!(ids, vec![event_ids[0], event_ids[2]]);
+            assert_eq!(page.total, 2);
+
+            // A re-indexed event is appended, and indexing twice adds nothing
+            contract.index_event_tags(event_ids[1], &science);
+            contract.index_event_tags(event_ids[1], &science);
+            let page = contract.get_events_by_tag_paged(String::from("Science"), 2, 10);
+            assert_eq!(page.events[0].id, event_ids[1]);
+            assert_eq!(page.total, 3);
+            assert_eq!(page.next_cursor, None);
+        }
+
+        #[ink::test]
+        fn query_event_ids_supports_any_and_all_tags() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id1 = contract.submit_event(
+                String::from("Event 1"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science"), String::from("Space")]
+            ).unwrap();
+            let event_id2 = contract.submit_event(
+                String::from("Event 2"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Science")]
+            ).unwrap();
+            let event_id3 = contract.submit_event(
+                String::from("Event 3"),
+                3000u64,
+                String::from("Description 3"),
+                vec![evidence("evidence3")],
+                vec![String::from("Politics")]
+            ).unwrap();
+
+            let any = contract.query_event_ids(EventFilter {
+                tags: vec![String::from("Space"), String::from("Politics")],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: None,
+                date_to: None,
+            }).unwrap();
+            assert_eq!(any, vec![event_id1, event_id3]);
+
+            let all = contract.query_event_ids(EventFilter {
+                tags: vec![String::from("Science"), String::from("Space")],
+                match_mode: TagMatch::All,
+                timeline: None,
+                date_from: None,
+                date_to: None,
+            }).unwrap();
+            assert_eq!(all, vec![event_id1]);
+
+            // A timeline alone is enough to query by
+            let pending = contract.query_event_ids(EventFilter {
+                tags: vec![],
+                match_mode: TagMatch::Any,
+                timeline: Some(Timeline::Pending),
+                date_from: None,
+                date_to: Some(2000),
+            }).unwrap();
+            assert_eq!(pending, vec![event_id1, event_id2]);
+
+            // Without tags or a timeline nothing bounds the scan
+            let everything = contract.query_event_ids(EventFilter {
+                tags: vec![],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: Some(1000),
+                date_to: None,
+            });
+            assert_eq!(everything, Err(Error::QueryTooBroad));
+        }
+
+        #[ink::test]
+        fn query_events_filters_by_timeline_and_date() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id1 = contract.submit_event(
+                String::from("Event 1"),
+                1000u64,
+                String::from("Description 1"),
+                vec![evidence("evidence1")],
+                vec![String::from("Science")]
+            ).unwrap();
+            let event_id2 = contract.submit_event(
+                String::from("Event 2"),
+                2000u64,
+                String::from("Description 2"),
+                vec![evidence("evidence2")],
+                vec![String::from("Science")]
+            ).unwrap();
+            let event_id3 = contract.submit_event(
+                String::from("Event 3"),
+                3000u64,
+                String::from("Description 3"),
+                vec![evidence("evidence3")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Move event 1 to Canonical
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id1, true).unwrap();
+            }
+
+            let pending = contract.query_events(EventFilter {
+                tags: vec![String::from("Science")],
+                match_mode: TagMatch::All,
+                timeline: Some(Timeline::Pending),
+                date_from: None,
+                date_to: None,
+            }, 0, 10).unwrap();
+            assert_eq!(pending.total, 2);
+            assert_eq!(pending.events[0].id, event_id2);
+            assert_eq!(pending.events[1].id, event_id3);
+
+            // The date range is applied per page; `total` counts the indexed matches
+            let dated = contract.query_events(EventFilter {
+                tags: vec![String::from("Science")],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: Some(1500),
+                date_to: Some(2500),
+            }, 0, 10).unwrap();
+            assert_eq!(dated.total, 3);
+            assert_eq!(dated.events.len(), 1);
+            assert_eq!(dated.events[0].id, event_id2);
+
+            // A page that reads only non-matching events is empty but keeps going
+            let first = contract.query_events(EventFilter {
+                tags: vec![String::from("Science")],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: Some(2500),
+                date_to: None,
+            }, 0, 2).unwrap();
+            assert!(first.events.is_empty());
+            assert_eq!(first.next_cursor, Some(2));
+            let second = contract.query_events(EventFilter {
+                tags: vec![String::from("Science")],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: Some(2500),
+                date_to: None,
+            }, 2, 2).unwrap();
+            assert_eq!(second.events[0].id, event_id3);
+            assert_eq!(second.next_cursor, None);
+        }
+
+        #[ink::test]
+        fn change_vote_moves_vote_to_other_side() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Canonical);
+
+            // Two voters switch to challenge: 1/3 = 33%
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.change_vote(event_id, false).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.change_vote(event_id, false).unwrap();
+
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.support_votes, 1);
+            assert_eq!(event.challenge_votes, 2);
+            assert_eq!(event.consensus_score, 33);
+            assert_eq!(event.timeline, Timeline::Disputed);
+            assert!(!contract.votes.get((event_id, accounts.charlie)).unwrap().support);
+        }
+
+        #[ink::test]
+        fn change_vote_fails_without_existing_vote() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            assert_eq!(contract.change_vote(event_id, false), Err(Error::NotVoted));
+            assert_eq!(contract.retract_vote(event_id), Err(Error::NotVoted));
+            assert_eq!(contract.change_vote(999, false), Err(Error::EventNotFound));
+        }
+
+        #[ink::test]
+        fn retract_vote_removes_vote_and_allows_revoting() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            contract.vote(event_id, false).unwrap();
+            contract.retract_vote(event_id).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(!contract.has_voted(event_id, accounts.alice));
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.support_votes, 0);
+            assert_eq!(event.challenge_votes, 0);
+            assert_eq!(event.consensus_score, 0);
+
+            contract.vote(event_id, true).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().support_votes, 1);
+        }
+
+        #[ink::test]
+        fn vote_changes_respect_cooldown() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                vote_change_cooldown: 1000,
+                ..Default::default()
+            }).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            contract.vote(event_id, true).unwrap();
+            assert_eq!(contract.change_vote(event_id, false), Err(Error::VoteCooldown));
+            assert_eq!(contract.retract_vote(event_id), Err(Error::VoteCooldown));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract.change_vote(event_id, false).unwrap();
+            assert_eq!(contract.retract_vote(event_id), Err(Error::VoteCooldown));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+            contract.retract_vote(event_id).unwrap();
+
+            // Re-voting after a retraction is also subject to the cooldown
+            assert_eq!(contract.vote(event_id, true), Err(Error::VoteCooldown));
+        }
+
+        #[ink::test]
+        fn single_vote_does_not_leave_pending() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // One account alone cannot canonize an event
+            contract.vote(event_id, true).unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.consensus_score, 100);
+            assert_eq!(event.timeline, Timeline::Pending);
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Pending);
+
+            // Reaching quorum places the event by score (2/3 = 66%)
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, true).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Disputed);
+
+            // Dropping below quorum returns it to Pending
+            contract.retract_vote(event_id).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Pending);
+        }
+
+        #[ink::test]
+        fn min_quorum_is_configurable() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                min_quorum: 5,
+                ..Default::default()
+            }).unwrap();
+            assert_eq!(contract.get_config().min_quorum, 5);
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Pending);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.vote(event_id, true).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Canonical);
+        }
+
+        #[ink::test]
+        fn with_config_rejects_invalid_thresholds() {
+            let result = HistoryProtocol::with_config(Config {
+                canonical_threshold: 40,
+                alternative_threshold: 60,
+                ..Default::default()
+            });
+            assert!(matches!(result, Err(Error::InvalidConfig)));
+
+            let result = HistoryProtocol::with_config(Config {
+                min_quorum: 0,
+                ..Default::default()
+            });
+            assert!(matches!(result, Err(Error::InvalidConfig)));
+        }
+
+        #[ink::test]
+        fn set_config_updates_thresholds() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let config = Config {
+                canonical_threshold: 60,
+                alternative_threshold: 20,
+                canonical_exit_threshold: 55,
+                alternative_exit_threshold: 25,
+                ..Default::default()
+            };
+            contract.set_config(config.clone()).unwrap();
+            assert_eq!(contract.get_config(), config);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            match <Event as scale::Decode>::decode(&mut &emitted_events[1].data[..]) {
+                Ok(Event::ConfigChanged(event)) => assert_eq!(event.config, config),
+                _ => panic!("expected ConfigChanged"),
+            }
+
+            // 2/3 = 66% is Canonical under the lowered threshold
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for (voter, support) in [(accounts.alice, true), (accounts.bob, true), (accounts.charlie, false)] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, support).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Canonical);
+        }
+
+        #[ink::test]
+        fn set_config_requires_owner() {
+            let mut contract = HistoryProtocol::new();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_config(Config::default()), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let invalid = Config {
+                canonical_threshold: 101,
+                ..Default::default()
+            };
+            assert_eq!(contract.set_config(invalid), Err(Error::InvalidConfig));
+        }
+
+        /// Builds an event with the given vote counts on the given timeline
+        fn event_with_votes(timeline: Timeline, support_votes: Balance, challenge_votes: Balance) -> HistoricalEvent {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let total_votes = support_votes + challenge_votes;
+            HistoricalEvent {
+                id: 1,
+                title: String::from("Test Event"),
+                date: 1000u64,
+                description: String::from("Description"),
+                evidence_sources: vec![evidence("evidence")],
+                tags: vec![String::from("Science")],
+                submitter: accounts.alice,
+                timeline,
+                consensus_score: (support_votes * 100 / total_votes) as u8,
+                support_votes,
+                challenge_votes,
+                voter_count: total_votes as u32,
+                created_at: 0,
+                hidden: false,
+                revision: 0,
+                substantive_revision: 0,
+                evidence_quality_score: None,
+            }
+        }
+
+        #[ink::test]
+        fn check_timeline_movement_enters_at_entry_thresholds() {
+            let mut contract = HistoryProtocol::new();
+
+            // 74% does not enter Canonical, 75% does
+            let mut event = event_with_votes(Timeline::Disputed, 74, 26);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Disputed);
+
+            let mut event = event_with_votes(Timeline::Disputed, 75, 25);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Canonical);
+
+            // 26% does not enter Alternative, 25% does
+            let mut event = event_with_votes(Timeline::Disputed, 26, 74);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Disputed);
+
+            let mut event = event_with_votes(Timeline::Disputed, 25, 75);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Alternative);
+        }
+
+        #[ink::test]
+        fn check_timeline_movement_applies_hysteresis_on_exit() {
+            let mut contract = HistoryProtocol::new();
+
+            // A Canonical event dipping to 70% stays Canonical
+            let mut event = event_with_votes(Timeline::Canonical, 70, 30);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Canonical);
+
+            // ...and only leaves below the exit threshold
+            let mut event = event_with_votes(Timeline::Canonical, 69, 31);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Disputed);
+
+            // An Alternative event rising to 30% stays Alternative
+            let mut event = event_with_votes(Timeline::Alternative, 30, 70);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Alternative);
+
+            let mut event = event_with_votes(Timeline::Alternative, 31, 69);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Disputed);
+
+            // A Canonical event collapsing past both bands goes straight to Alternative
+            let mut event = event_with_votes(Timeline::Canonical, 10, 90);
+            contract.check_timeline_movement(&mut event);
+            assert_eq!(event.timeline, Timeline::Alternative);
+        }
+
+        #[ink::test]
+        fn close_votes_do_not_flap_between_timelines() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // 3 support + 1 challenge = 75%: enters Canonical
+            let mut votes = vec![true, true, true, false];
+            // 5 support + 2 challenge = 71%: would be Disputed without hysteresis
+            votes.extend([true, true, false]);
+            for (i, support) in votes.into_iter().enumerate() {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(AccountId::from([i as u8 + 1; 32]));
+                contract.vote(event_id, support).unwrap();
+                if i == 3 {
+                    assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Canonical);
+                }
+            }
+
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.consensus_score, 71);
+            assert_eq!(event.timeline, Timeline::Canonical);
+
+            // Only one TimelineChanged was emitted for all those votes
+            let timeline_changes = ink::env::test::recorded_events()
+                .filter(|e| matches!(<Event as scale::Decode>::decode(&mut &e.data[..]), Ok(Event::TimelineChanged(_))))
+                .count();
+            assert_eq!(timeline_changes, 1);
+        }
+
+        #[ink::test]
+        fn get_timeline_history_records_transitions() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+            assert!(contract.get_timeline_history(event_id, 0, 10).is_empty());
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            for voter in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.change_vote(event_id, false).unwrap();
+            }
+
+            let history = contract.get_timeline_history(event_id, 0, 10);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].from, Timeline::Pending);
+            assert_eq!(history[0].to, Timeline::Canonical);
+            assert_eq!(history[0].consensus_score, 100);
+            assert_eq!(history[0].block_number, 0);
+            assert_eq!(history[1].from, Timeline::Canonical);
+            assert_eq!(history[1].to, Timeline::Disputed);
+            assert_eq!(history[1].consensus_score, 66);
+            assert_eq!(history[1].block_number, 1);
+            assert_eq!(history[1].timestamp, 5000);
+
+            // Pages continue from the cursor
+            assert_eq!(contract.get_timeline_history(event_id, 1, 1), history[1..].to_vec());
+            assert!(contract.get_timeline_history(event_id, 2, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn timeline_history_outgrows_a_storage_cell() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                min_quorum: 1,
+                ..Default::default()
+            }).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // Each round trip records two transitions; together they would not fit one cell
+            for _ in 0..400 {
+                contract.vote(event_id, true).unwrap();
+                contract.retract_vote(event_id).unwrap();
+            }
+
+            let last = contract.get_timeline_history(event_id, 799, 10);
+            assert_eq!(last.len(), 1);
+            assert_eq!(last[0].to, Timeline::Pending);
+            assert_eq!(contract.get_timeline_history(event_id, 0, u32::MAX).len(), MAX_PAGE_SIZE as usize);
+        }
+
+        #[ink::test]
+        fn token_weighted_votes_use_transferred_value() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { min_quorum: 2, ..Default::default() },
+                VotingMode::TokenWeighted,
+            ).unwrap();
+            assert_eq!(contract.get_voting_mode(), VotingMode::TokenWeighted);
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // A vote without stake is rejected
+            assert_eq!(contract.vote(event_id, true), Err(Error::InsufficientStake));
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.vote(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            contract.vote(event_id, false).unwrap();
+
+            // 100 / 400 = 25%
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.support_votes, 100);
+            assert_eq!(event.challenge_votes, 300);
+            assert_eq!(event.voter_count, 2);
+            assert_eq!(event.consensus_score, 25);
+            assert_eq!(event.timeline, Timeline::Alternative);
+
+            // Changing the vote moves its full weight: 400 / 400 = 100%
+            contract.change_vote(event_id, true).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().consensus_score, 100);
+        }
+
+        #[ink::test]
+        fn token_weighted_retract_refunds_stake() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config::default(),
+                VotingMode::TokenWeighted,
+            ).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 500);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.vote(event_id, false).unwrap();
+
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            contract.retract_vote(event_id).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+
+            assert_eq!(balance_after - balance_before, 500);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.challenge_votes, 0);
+            assert_eq!(event.voter_count, 0);
+        }
+
+        #[ink::test]
+        fn one_account_one_vote_rejects_value() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.vote(event_id, true), Err(Error::UnexpectedValue));
+        }
+
+        #[ink::test]
+        fn credit_spend_history_outgrows_a_storage_cell() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { credits_per_period: 1000, ..Default::default() },
+                VotingMode::Quadratic,
+            ).unwrap();
+
+            // More spends than one cell could hold as a single list
+            for _ in 0..500 {
+                let event_id = contract.submit_event(
+                    String::from("Test Event"),
+                    1000u64,
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from("Science")]
+                ).unwrap();
+                contract.vote(event_id, true).unwrap();
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(contract.get_vote_credits(accounts.alice), 500);
+            let last = contract.get_credit_spends(accounts.alice, 499, 10);
+            assert_eq!(last.len(), 1);
+            assert_eq!(last[0].event_id, 500);
+            assert_eq!(contract.get_credit_spends(accounts.alice, 0, u32::MAX).len(), MAX_PAGE_SIZE as usize);
+        }
+
+        #[ink::test]
+        fn quadratic_votes_cost_squared_credits() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { min_quorum: 2, ..Default::default() },
+                VotingMode::Quadratic,
+            ).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(contract.get_vote_credits(accounts.alice), 100);
+
+            // 11 votes would cost 121 credits
+            assert_eq!(contract.cast_quadratic_vote(event_id, true, 11), Err(Error::InsufficientCredits));
+            assert_eq!(contract.cast_quadratic_vote(event_id, true, 0), Err(Error::InvalidVoteCount));
+
+            // 6 votes cost 36 credits
+            contract.cast_quadratic_vote(event_id, true, 6).unwrap();
+            assert_eq!(contract.get_vote_credits(accounts.alice), 64);
+            assert_eq!(contract.get_credit_spends(accounts.alice, 0, 10), vec![CreditSpend {
+                event_id,
+                amendment_id: None,
+                support: true,
+                votes: 6,
+                credits: 36,
+                timestamp: 0,
+            }]);
+
+            // A plain vote counts once for one credit
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+            assert_eq!(contract.get_vote_credits(accounts.bob), 99);
+
+            // 6 / 7 = 85%
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.support_votes, 6);
+            assert_eq!(event.challenge_votes, 1);
+            assert_eq!(event.consensus_score, 85);
+            assert_eq!(event.timeline, Timeline::Canonical);
+        }
+
+        #[ink::test]
+        fn quadratic_credits_refill_each_period() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { credit_period: 1000, ..Default::default() },
+                VotingMode::Quadratic,
+            ).unwrap();
+
+            for _ in 0..2 {
+                contract.submit_event(
+                    String::from("Test Event"),
+                    1000u64,
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from("Science")]
+                ).unwrap();
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.cast_quadratic_vote(1, true, 10).unwrap();
+            assert_eq!(contract.get_vote_credits(accounts.alice), 0);
+            assert_eq!(contract.cast_quadratic_vote(2, true, 1), Err(Error::InsufficientCredits));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.get_vote_credits(accounts.alice), 100);
+            contract.cast_quadratic_vote(2, true, 1).unwrap();
+            assert_eq!(contract.get_vote_credits(accounts.alice), 99);
+        }
+
+        #[ink::test]
+        fn cast_quadratic_vote_requires_quadratic_mode() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            assert_eq!(contract.cast_quadratic_vote(event_id, true, 2), Err(Error::WrongVotingMode));
+        }
+
+        #[ink::test]
+        fn conviction_multiplies_vote_weight() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { min_quorum: 2, ..Default::default() },
+                VotingMode::TokenWeighted,
+            ).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            // 100 locked at 2x outweighs 1000 with no conviction (0.1x)
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.vote_with_conviction(event_id, true, Conviction::Locked2x).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.vote(event_id, false).unwrap();
+
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.support_votes, 2000);
+            assert_eq!(event.challenge_votes, 1000);
+            assert_eq!(event.consensus_score, 66);
+
+            let vote = contract.votes.get((event_id, accounts.alice)).unwrap();
+            assert_eq!(vote.conviction, Conviction::Locked2x);
+            assert_eq!(vote.stake, 100);
+            assert_eq!(vote.locked_until, 2 * 86_400_000);
+        }
+
+        #[ink::test]
+        fn locked_stake_is_released_by_unlock_after_expiry() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { conviction_lock_period: 1000, ..Default::default() },
+                VotingMode::TokenWeighted,
+            ).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 300);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            contract.vote_with_conviction(event_id, true, Conviction::Locked3x).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            // Retracting removes the vote but keeps the stake locked for 4 periods
+            contract.retract_vote(event_id).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().support_votes, 0);
+            assert_eq!(contract.get_stake_locks(accounts.bob), vec![StakeLock {
+                amount: 300,
+                locked_until: 4000,
+            }]);
+            assert_eq!(contract.unlock(), Ok(0));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4000);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(contract.unlock(), Ok(300));
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(balance_after - balance_before, 300);
+            assert!(contract.get_stake_locks(accounts.bob).is_empty());
+        }
+
+        #[ink::test]
+        fn vote_with_conviction_requires_token_weighted_mode() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            assert_eq!(
+                contract.vote_with_conviction(event_id, true, Conviction::Locked1x),
+                Err(Error::WrongVotingMode)
+            );
+        }
+
+        #[ink::test]
+        fn submit_event_requires_deposit() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 100,
+                ..Default::default()
+            }).unwrap();
+
+            let result = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::InsufficientDeposit));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            assert_eq!(contract.get_deposit(event_id), Some(SubmissionDeposit {
+                amount: 100,
+                status: DepositStatus::Held,
+                slashed: 0,
+            }));
+        }
+
+        #[ink::test]
+        fn settle_deposit_refunds_accepted_events() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 100,
+                review_window: 1000,
+                ..Default::default()
+            }).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 100);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            // Pending events cannot be settled
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.settle_deposit(event_id), Err(Error::EventStillPending));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+            assert_eq!(contract.settle_deposit(event_id), Err(Error::ReviewWindowOpen));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+            contract.settle_deposit(event_id).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+
+            assert_eq!(balance_after - balance_before, 100);
+            assert_eq!(contract.get_deposit(event_id).unwrap().status, DepositStatus::Refunded);
+            assert_eq!(contract.settle_deposit(event_id), Err(Error::DepositAlreadySettled));
+        }
+
+        #[ink::test]
+        fn settle_deposit_slashes_rejected_events() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 100,
+                review_window: 0,
+                slash_percent: 40,
+                ..Default::default()
+            }).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 100);
+            contract.set_treasury(accounts.frank).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            let event_id = contract.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, false).unwrap();
+            }
+
+            let treasury_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            let submitter_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+            contract.settle_deposit(event_id).unwrap();
+            let treasury_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            let submitter_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+
+            assert_eq!(treasury_after - treasury_before, 40);
+            assert_eq!(submitter_after - submitter_before, 60);
+            assert_eq!(contract.get_deposit(event_id), Some(SubmissionDeposit {
+                amount: 100,
+                status: DepositStatus::Slashed,
+                slashed: 40,
+            }));
+        }
+
+        #[ink::test]
+        fn submit_event_enforces_field_lengths() {
+            let mut contract = HistoryProtocol::new();
+
+            let long = |len: usize| "x".repeat(len);
+
+            let result = contract.submit_event(
+                long(MAX_TITLE_LENGTH + 1),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::TitleTooLong));
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                long(MAX_DESCRIPTION_LENGTH + 1),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::DescriptionTooLong));
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence"), evidence(&long(MAX_EVIDENCE_LENGTH + 1))],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::EvidenceTooLong));
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![long(MAX_TAG_LENGTH + 1)]
+            );
+            assert_eq!(result, Err(Error::TagTooLong));
+
+            // Exactly at the limits is accepted
+            let result = contract.submit_event(
+                long(MAX_TITLE_LENGTH),
+                1000u64,
+                long(MAX_DESCRIPTION_LENGTH),
+                vec![evidence(&long(MAX_EVIDENCE_LENGTH))],
+                vec![long(MAX_TAG_LENGTH)]
+            );
+            assert!(result.is_ok());
+            assert_eq!(contract.event_count, 1);
+        }
+
+        #[ink::test]
+        fn deposit_scales_with_event_size() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 100,
+                deposit_per_byte: 2,
+                ..Default::default()
+            }).unwrap();
+
+            let short = contract.estimate_deposit(
+                String::from("Title"),
+                String::from("Short"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            let long = contract.estimate_deposit(
+                String::from("Title"),
+                "x".repeat(1000),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            // 995 more description bytes plus one more byte of length prefix
+            assert_eq!(long - short, 2 * 996);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(long - 1);
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                "x".repeat(1000),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::InsufficientDeposit));
+            assert_eq!(contract.event_count, 0);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(long);
+            let event_id = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                "x".repeat(1000),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+            assert_eq!(contract.get_deposit(event_id).unwrap().amount, long);
+        }
+
+        #[ink::test]
+        fn submit_event_rejects_empty_and_duplicate_entries() {
+            let mut contract = HistoryProtocol::new();
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence"), evidence("")],
+                vec![String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::EmptyEvidence));
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science"), String::from("")]
+            );
+            assert_eq!(result, Err(Error::EmptyTag));
+
+            let result = contract.submit_event(
+                String::from("Title"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science"), String::from("Space"), String::from("Science")]
+            );
+            assert_eq!(result, Err(Error::DuplicateTag));
+            assert_eq!(contract.event_count, 0);
+        }
+
+        #[ink::test]
+        fn ownership_transfer_requires_acceptance() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            assert_eq!(contract.owner(), Some(accounts.alice));
+
+            // Only the owner can nominate
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.transfer_ownership(accounts.bob), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.transfer_ownership(accounts.bob).unwrap();
+            assert_eq!(contract.pending_owner(), Some(accounts.bob));
+            // Nothing changes until the nominee accepts
+            assert_eq!(contract.owner(), Some(accounts.alice));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.accept_ownership(), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.accept_ownership().unwrap();
+            assert_eq!(contract.owner(), Some(accounts.bob));
+            assert_eq!(contract.pending_owner(), None);
+
+            // The previous owner lost its rights
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_treasury(accounts.alice), Err(Error::Unauthorized));
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let transferred = match <Event as scale::Decode>::decode(&mut &emitted_events.last().unwrap().data[..]) {
+                Ok(Event::OwnershipTransferred(event)) => event,
+                _ => panic!("expected OwnershipTransferred"),
+            };
+            assert_eq!(transferred.previous_owner, Some(accounts.alice));
+            assert_eq!(transferred.new_owner, Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn renounce_ownership_freezes_admin_messages() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+
+            contract.transfer_ownership(accounts.bob).unwrap();
+            contract.renounce_ownership().unwrap();
+            assert_eq!(contract.owner(), None);
+            assert_eq!(contract.pending_owner(), None);
+
+            assert_eq!(contract.set_config(Config::default()), Err(Error::Unauthorized));
+            assert_eq!(contract.renounce_ownership(), Err(Error::Unauthorized));
+
+            // A nomination made before renouncing can no longer be accepted
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.accept_ownership(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn roles_are_managed_by_owner_and_admins() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+
+            contract.grant_role(accounts.bob, Role::Admin).unwrap();
+            assert!(contract.has_role(accounts.bob, Role::Admin));
+
+            // Admins manage the other roles but not Admin itself
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.grant_role(accounts.charlie, Role::Moderator).unwrap();
+            assert!(contract.has_role(accounts.charlie, Role::Moderator));
+            assert_eq!(contract.grant_role(accounts.charlie, Role::Admin), Err(Error::Unauthorized));
+            contract.revoke_role(accounts.charlie, Role::Moderator).unwrap();
+            assert!(!contract.has_role(accounts.charlie, Role::Moderator));
+
+            // Accounts without a role cannot grant anything
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.grant_role(accounts.charlie, Role::Verifier), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.revoke_role(accounts.bob, Role::Admin).unwrap();
+            assert!(!contract.has_role(accounts.bob, Role::Admin));
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 4);
+            let revoked = match <Event as scale::Decode>::decode(&mut &emitted_events[3].data[..]) {
+                Ok(Event::RoleRevoked(event)) => event,
+                _ => panic!("expected RoleRevoked"),
+            };
+            assert_eq!(revoked.account, accounts.bob);
+            assert_eq!(revoked.role, Role::Admin);
+            assert_eq!(revoked.revoked_by, accounts.alice);
+        }
+
+        #[ink::test]
+        fn moderator_hides_events_from_listings() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            contract.grant_role(accounts.bob, Role::Moderator).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Spam"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            assert_eq!(contract.set_event_hidden(event_id, true), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.set_event_hidden(event_id, true).unwrap();
+            assert!(contract.get_event(event_id).unwrap().hidden);
+            assert!(contract.get_events_by_tag(String::from("Science")).is_empty());
+            assert!(contract.get_events_by_timeline(Timeline::Pending).is_empty());
+            assert!(contract.get_user_events_paged(accounts.alice, 0, 10).events.is_empty());
+
+            // Ids, totals and tag counts leave it out as well
+            assert_eq!(contract.get_events_by_timeline_paged(Timeline::Pending, 0, 10).total, 0);
+            assert!(contract.list_tags().is_empty());
+            assert_eq!(contract.query_event_ids(EventFilter {
+                tags: vec![String::from("Science")],
+                match_mode: TagMatch::Any,
+                timeline: None,
+                date_from: None,
+                date_to: None,
+            }), Ok(vec![]));
+
+            // Votes can still move it, without putting it back in a timeline list
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Canonical);
+            assert_eq!(contract.get_events_by_timeline_paged(Timeline::Canonical, 0, 10).total, 0);
+
+            // Restoring re-indexes it under its current timeline
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.set_event_hidden(event_id, false).unwrap();
+            assert_eq!(contract.get_events_by_tag(String::from("Science")).len(), 1);
+            assert_eq!(contract.get_events_by_timeline(Timeline::Canonical).len(), 1);
+            assert!(contract.get_events_by_timeline(Timeline::Pending).is_empty());
+            assert_eq!(contract.get_user_events(accounts.alice).len(), 1);
+            assert_eq!(contract.list_tags(), vec![TagInfo { tag: String::from("Science"), event_count: 1 }]);
+        }
+
+        #[ink::test]
+        fn curator_replaces_event_tags() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            contract.grant_role(accounts.bob, Role::Curator).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
+            let new_tags = vec![String::from("Space"), String::from("Technology")];
+            assert_eq!(contract.set_event_tags(event_id, new_tags.clone()), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_event_tags(event_id, vec![]), Err(Error::NoTags));
+            contract.set_event_tags(event_id, new_tags.clone()).unwrap();
+
+            assert_eq!(contract.get_event(event_id).unwrap().tags, new_tags);
+            assert!(contract.get_events_by_tag(String::from("Science")).is_empty());
+            assert_eq!(contract.get_events_by_tag(String::from("Space")).len(), 1);
+            assert_eq!(contract.list_tags(), vec![
+                TagInfo { tag: String::from("Space"), event_count: 1 },
+                TagInfo { tag: String::from("Technology"), event_count: 1 },
+            ]);
+        }
+
+        #[ink::test]
+        fn verifier_attests_evidence_once() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            contract.grant_role(accounts.bob, Role::Verifier).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence1"), evidence("evidence2")],
+                vec![String::from("Space")]
+            ).unwrap();
+
+            assert_eq!(contract.attest_evidence(event_id, 0), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.attest_evidence(event_id, 2), Err(Error::EvidenceNotFound));
+            assert_eq!(contract.attest_evidence(999, 0), Err(Error::EventNotFound));
+            contract.attest_evidence(event_id, 1).unwrap();
+            assert_eq!(contract.attest_evidence(event_id, 1), Err(Error::AlreadyAttested));
+
+            let attestations = contract.get_evidence_attestations(event_id);
+            assert_eq!(attestations.len(), 1);
+            assert_eq!(attestations[0].evidence_index, 1);
+            assert_eq!(attestations[0].verifier, accounts.bob);
+        }
+
+        #[ink::test]
+        fn attestations_follow_the_source_through_amendments() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            contract.grant_role(accounts.eve, Role::Verifier).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("good"), evidence("other")],
+                vec![String::from("Space")]
+            ).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.attest_evidence(event_id, 0).unwrap();
+
+            // Replace the attested source and move the other one into its place
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Description"),
+                vec![evidence("other"), evidence("forged")],
+                vec![String::from("Space")]
+            ).unwrap();
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            assert_eq!(contract.get_amendment(amendment_id).unwrap().status, AmendmentStatus::Applied);
+            assert!(contract.get_evidence_attestations(event_id).is_empty());
+
+            // The same verifier can attest the new source at that position
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.attest_evidence(event_id, 1).unwrap();
+            let attestations = contract.get_evidence_attestations(event_id);
+            assert_eq!(attestations.len(), 1);
+            assert_eq!(attestations[0].evidence_index, 1);
+        }
+
+        #[ink::test]
+        fn pause_blocks_mutating_messages() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            contract.grant_role(accounts.bob, Role::Admin).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            contract.vote(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.pause(), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.pause().unwrap();
+            assert!(contract.is_paused());
+
+            let result = contract.submit_event(
+                String::from("Another Event"),
+                2000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::Paused));
+            assert_eq!(contract.vote(event_id, true), Err(Error::Paused));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.change_vote(event_id, false), Err(Error::Paused));
+            assert_eq!(contract.retract_vote(event_id), Err(Error::Paused));
+
+            // Queries keep working
+            assert_eq!(contract.get_event(event_id).unwrap().voter_count, 1);
+            assert!(contract.has_voted(event_id, accounts.alice));
+
+            contract.unpause().unwrap();
+            assert!(!contract.is_paused());
+            contract.retract_vote(event_id).unwrap();
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let paused_events = emitted_events
+                .iter()
+                .filter(|e| matches!(
+                    <Event as scale::Decode>::decode(&mut &e.data[..]),
+                    Ok(Event::Paused(_)) | Ok(Event::Unpaused(_))
+                ))
+                .count();
+            assert_eq!(paused_events, 2);
+        }
+
+        #[ink::test]
+        fn upgrade_and_migrate_require_owner() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.upgrade(Hash::from([1u8; 32])), Err(Error::Unauthorized));
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Err(Error::Unauthorized));
+
+            // Migrating current storage changes nothing
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Ok(STORAGE_VERSION));
+            assert_eq!(ink::env::test::recorded_events().count(), 0);
+        }
+
+        #[ink::test]
+        fn mismatched_storage_version_blocks_mutations() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+
+            // Storage written by newer code cannot be migrated backwards
+            contract.storage_version.set(&(STORAGE_VERSION + 1));
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Err(Error::UnsupportedStorageVersion));
+
+            let result = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::MigrationPending));
+        }
 
         #[ink::test]
-        fn new_works() {
+        fn root_cell_keeps_the_version_0_layout() {
             let contract = HistoryProtocol::new();
-            assert_eq!(contract.event_count, 0);
+
+            // Everything but `event_count` lives outside the root cell
+            let mut root = Vec::new();
+            ink::storage::traits::Storable::encode(&contract, &mut root);
+            assert_eq!(root, scale::Encode::encode(&0u64));
+
+            // Unchanged mappings keep their version 0 keys; the legacy keys match them
+            use ink::storage::traits::StorageKey;
+            assert_eq!(
+                contract.timeline_events.key(),
+                KeyComposer::from_str("HistoryProtocol::timeline_events")
+            );
+            assert_eq!(contract.legacy_events.key(), LEGACY_EVENTS_KEY);
+            assert_ne!(contract.events.key(), LEGACY_EVENTS_KEY);
+            assert_ne!(contract.votes.key(), LEGACY_VOTES_KEY);
         }
 
         #[ink::test]
-        fn submit_event_works() {
+        fn version_0_storage_is_migrated() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            let title = String::from("First Moon Landing");
-            let date = 1969_07_20u64;
-            let description = String::from("Apollo 11 successfully landed on the moon");
-            let evidence = vec![
-                String::from("https://nasa.gov/apollo11"),
-                String::from("https://archive.org/moon-landing")
-            ];
-            let tags = vec![
-                String::from("Space"),
-                String::from("Science")
-            ];
 
+            // Recreate what version 0 code left behind: no owner, no version, old layouts
+            contract.storage_version.set(&0);
+            contract.owner.set(&None);
+            for (id, title, tag) in [(1, "Moon Landing", "Space"), (2, "Printing Press", "Technology")] {
+                contract.legacy_events.insert(id, &LegacyEvent {
+                    id,
+                    title: String::from(title),
+                    date: 1000,
+                    description: String::from("Description"),
+                    evidence_sources: vec![
+                        String::from("https://nasa.gov/apollo11"),
+                        String::from("NASA mission report"),
+                    ],
+                    tags: vec![String::from(tag)],
+                    submitter: accounts.alice,
+                    timeline: Timeline::Disputed,
+                    consensus_score: if id == 1 { 66 } else { 0 },
+                    support_votes: if id == 1 { 2 } else { 0 },
+                    challenge_votes: if id == 1 { 1 } else { 0 },
+                    created_at: 0,
+                });
+            }
+            // Eve's vote is counted, but nobody can find it to migrate
+            for (voter, support) in [(accounts.bob, true), (accounts.charlie, false), (accounts.eve, true)] {
+                contract.legacy_votes.insert((1, voter), &LegacyVote {
+                    voter,
+                    event_id: 1,
+                    support,
+                    timestamp: 5,
+                });
+            }
+            contract.event_count = 2;
+            contract.timeline_events.insert(Timeline::Disputed, &vec![1, 2]);
+
+            // Only the account fixed at build time can claim the ownerless storage
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.migrate(1), Err(Error::Unauthorized));
+            let owner = AccountId::from(VERSION_0_OWNER);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(owner);
+            assert_eq!(contract.migrate(1), Ok(0));
+            assert_eq!(contract.owner(), Some(owner));
+            assert_eq!(contract.get_treasury(), owner);
             let result = contract.submit_event(
-                title.clone(),
-                date,
-                description.clone(),
-                evidence.clone(),
-                tags.clone()
+                String::from("Magna Carta"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Law")]
             );
+            assert_eq!(result, Err(Error::MigrationPending));
 
-            assert!(result.is_ok());
-            let event_id = result.unwrap();
-            assert_eq!(event_id, 1);
-            assert_eq!(contract.event_count, 1);
+            // All events are converted, but the version waits for their votes
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Ok(0));
+            assert!(contract.legacy_events.get(1).is_none());
+            let event = contract.get_event(1).unwrap();
+            assert_eq!(event.evidence_sources[0].kind, EvidenceKind::Url);
+            assert_eq!(event.evidence_sources[1].kind, EvidenceKind::Archive);
+            assert_eq!(event.support_votes, 2);
+            assert_eq!(event.voter_count, 3);
+            assert_eq!(contract.get_events_by_tag(String::from("Technology")).len(), 1);
 
-            // Verify event was stored correctly
-            let stored_event = contract.events.get(event_id);
-            assert!(stored_event.is_some());
-            
-            let event = stored_event.unwrap();
-            assert_eq!(event.id, event_id);
-            assert_eq!(event.title, title);
-            assert_eq!(event.date, date);
-            assert_eq!(event.description, description);
-            assert_eq!(event.evidence_sources, evidence);
-            assert_eq!(event.tags, tags);
-            assert_eq!(event.timeline, Timeline::Disputed);
-            assert_eq!(event.consensus_score, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.migrate_votes(1, vec![accounts.bob]), Err(Error::Unauthorized));
+            assert_eq!(contract.discard_unmigrated_votes(1), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(owner);
+            assert_eq!(contract.migrate_votes(1, vec![accounts.django]), Err(Error::NotVoted));
+            assert_eq!(contract.migrate_votes(1, vec![accounts.bob, accounts.charlie]), Ok(()));
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Ok(0));
+
+            // The vote that cannot be found is dropped from the tallies instead
+            assert_eq!(contract.discard_unmigrated_votes(1), Ok(1));
+            assert_eq!(contract.discard_unmigrated_votes(1), Ok(0));
+            let event = contract.get_event(1).unwrap();
+            assert_eq!(event.support_votes, 1);
+            assert_eq!(event.challenge_votes, 1);
+            assert_eq!(event.voter_count, 2);
+            assert_eq!(event.consensus_score, 50);
+
+            assert_eq!(contract.migrate(MAX_PAGE_SIZE), Ok(STORAGE_VERSION));
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(contract.migrate_votes(1, vec![accounts.bob]), Err(Error::UnsupportedStorageVersion));
+            let votes = contract.export_votes(1, 0, MAX_PAGE_SIZE);
+            assert_eq!(votes.len(), 2);
+            assert!(votes.iter().all(|vote| vote.weight == 1 && vote.stake == 0));
+
+            // Migrated votes behave like current ones
+            let cooldown = contract.get_config().vote_change_cooldown;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5 + cooldown);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.retract_vote(1), Ok(()));
+            let event = contract.get_event(1).unwrap();
             assert_eq!(event.support_votes, 0);
-            assert_eq!(event.challenge_votes, 0);
+            assert_eq!(event.voter_count, 1);
         }
 
         #[ink::test]
-        fn submit_event_fails_with_empty_title() {
-            let mut contract = HistoryProtocol::new();
-            
-            let result = contract.submit_event(
-                String::from(""),
-                1969_07_20u64,
+        fn export_and_import_moves_events_and_votes() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut source = HistoryProtocol::new();
+
+            for (title, tag) in [("Moon Landing", "Space"), ("Printing Press", "Technology")] {
+                source.submit_event(
+                    String::from(title),
+                    1000u64,
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from(tag)]
+                ).unwrap();
+            }
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                source.vote(2, true).unwrap();
+            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            source.retract_vote(2).unwrap();
+
+            let exported_events = source.export_events(1, 10);
+            assert_eq!(exported_events.len(), 2);
+            assert_eq!(source.export_events(2, 10).len(), 1);
+            let exported_votes = source.export_votes(2, 0, 10);
+            assert_eq!(exported_votes.len(), 2);
+            assert_eq!(source.export_votes(2, 1, 10)[0].voter, accounts.charlie);
+
+            // The new deployment lives at another address with its own storage
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0x42; 32]));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let mut target = HistoryProtocol::new();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(target.import_events(exported_events.clone()), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(target.import_votes(exported_votes.clone()), Err(Error::EventNotFound));
+            target.import_events(exported_events.clone()).unwrap();
+            assert_eq!(target.import_events(exported_events), Err(Error::EventAlreadyExists));
+            target.import_votes(exported_votes.clone()).unwrap();
+            assert_eq!(target.import_votes(exported_votes), Err(Error::AlreadyVoted));
+
+            let event = target.get_event(2).unwrap();
+            assert_eq!(event.submitter, accounts.alice);
+            assert_eq!(event.voter_count, 2);
+            assert_eq!(target.get_user_events(accounts.alice).len(), 2);
+            assert_eq!(target.get_events_by_timeline(Timeline::Pending).len(), 2);
+            assert_eq!(target.get_events_by_tag(String::from("Space"))[0].id, 1);
+            assert!(target.has_voted(2, accounts.charlie));
+            assert!(!target.has_voted(2, accounts.bob));
+
+            // New submissions continue after the imported IDs
+            let event_id = target.submit_event(
+                String::from("Fall of Rome"),
+                1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                vec![evidence("evidence")],
+                vec![String::from("Politics")]
+            ).unwrap();
+            assert_eq!(event_id, 3);
+        }
+
+        #[ink::test]
+        fn submitter_revises_event_within_grace_period() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_config(Config {
+                revision_grace_period: 1000,
+                ..Default::default()
+            }).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landnig"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            contract.vote(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             );
+            assert_eq!(result, Err(Error::Unauthorized));
 
-            assert_eq!(result, Err(Error::InvalidEventData));
-            assert_eq!(contract.event_count, 0);
+            // A typo fix within the grace period leaves votes current
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let revision = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(revision, 1);
+            assert_eq!(contract.get_event(event_id).unwrap().title, "Moon Landing");
+            assert!(!contract.is_vote_outdated(event_id, accounts.alice));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1001);
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing 1969"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::GracePeriodExpired));
+
+            let revisions = contract.get_event_revisions(event_id, 0, 10);
+            assert_eq!(revisions.len(), 2);
+            assert_eq!(revisions[0].title, "Moon Landnig");
+            assert_eq!(revisions[1].author, accounts.alice);
+            assert!(!revisions[1].substantive);
+            assert_eq!(contract.get_event_at_revision(event_id, 0).unwrap().title, "Moon Landnig");
+            assert!(contract.get_event_at_revision(event_id, 2).is_none());
         }
 
         #[ink::test]
-        fn submit_event_fails_with_empty_description() {
+        fn substantive_revision_outdates_earlier_votes() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            let result = contract.submit_event(
-                String::from("Title"),
-                1969_07_20u64,
-                String::from(""),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(contract.get_event_revisions(event_id, 0, 10).len(), 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, true).unwrap();
+
+            // The submitter can't rewrite the event directly, even within the grace period
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let content = (
+                String::from("Apollo 11 landed in the Sea of Tranquility"),
+                vec![evidence("evidence"), url("https://nasa.gov")],
+                vec![String::from("Space"), String::from("Technology")],
+            );
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                content.0.clone(),
+                content.1.clone(),
+                content.2.clone()
             );
+            assert_eq!(result, Err(Error::SubstantiveEdit));
 
-            assert_eq!(result, Err(Error::InvalidEventData));
-            assert_eq!(contract.event_count, 0);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                content.0,
+                content.1,
+                content.2
+            ).unwrap();
+            for voter in [accounts.alice, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+
+            assert!(contract.is_vote_outdated(event_id, accounts.bob));
+            assert_eq!(contract.get_events_by_tag(String::from("Technology")).len(), 1);
+
+            // Voting after the revision counts as current
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote(event_id, false).unwrap();
+            assert!(!contract.is_vote_outdated(event_id, accounts.charlie));
+
+            // Changing a vote moves it onto the current revision
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.change_vote(event_id, false).unwrap();
+            assert!(!contract.is_vote_outdated(event_id, accounts.bob));
         }
 
         #[ink::test]
-        fn submit_event_fails_with_no_evidence() {
+        fn amendment_is_applied_when_vote_passes() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            let result = contract.submit_event(
-                String::from("Title"),
-                1969_07_20u64,
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
                 String::from("Description"),
-                vec![],
-                vec![String::from("Science")]
-            );
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
 
-            assert_eq!(result, Err(Error::InvalidEventData));
-            assert_eq!(contract.event_count, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Apollo 11 landed on 20 July 1969"),
+                vec![evidence("evidence"), url("https://nasa.gov")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(contract.get_event_amendments(event_id, 0, 10).len(), 1);
+
+            contract.vote_amendment(amendment_id, true).unwrap();
+            assert_eq!(contract.vote_amendment(amendment_id, true), Err(Error::AlreadyVoted));
+            assert_eq!(contract.get_amendment_vote(amendment_id, accounts.bob), Some(AmendmentVote {
+                support: true,
+                weight: 1,
+                stake: 0,
+            }));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.vote_amendment(amendment_id, true).unwrap();
+            // Below quorum nothing changes yet
+            assert_eq!(contract.get_amendment(amendment_id).unwrap().status, AmendmentStatus::Open);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            contract.vote_amendment(amendment_id, false).unwrap();
+
+            // 2 of 3 in favour reaches the default 66% threshold
+            let amendment = contract.get_amendment(amendment_id).unwrap();
+            assert_eq!(amendment.status, AmendmentStatus::Applied);
+            assert!(amendment.substantive);
+            assert_eq!(amendment.applied_revision, Some(1));
+
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.description, "Apollo 11 landed on 20 July 1969");
+            assert_eq!(event.substantive_revision, 1);
+            assert_eq!(contract.get_event_revisions(event_id, 0, 10)[1].author, accounts.bob);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.vote_amendment(amendment_id, true), Err(Error::AmendmentClosed));
         }
 
         #[ink::test]
-        fn event_id_generation_is_unique() {
+        fn amendment_is_rejected_or_superseded() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            let title = String::from("Event");
-            let date = 1000u64;
-            let description = String::from("Description");
-            let evidence = vec![String::from("evidence")];
-            let tags = vec![String::from("Science")];
 
-            // Submit first event
-            let result1 = contract.submit_event(
-                title.clone(),
-                date,
-                description.clone(),
-                evidence.clone(),
-                tags.clone()
-            );
-            assert!(result1.is_ok());
-            let event_id1 = result1.unwrap();
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
 
-            // Submit second event
-            let result2 = contract.submit_event(
-                title.clone(),
-                date,
-                description.clone(),
-                evidence.clone(),
-                tags.clone()
-            );
-            assert!(result2.is_ok());
-            let event_id2 = result2.unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let rejected = contract.propose_amendment(
+                event_id,
+                String::from("Moon Hoax"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            let superseded = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing 1969"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
 
-            // Verify IDs are unique
-            assert_eq!(event_id1, 1);
-            assert_eq!(event_id2, 2);
-            assert_ne!(event_id1, event_id2);
-            assert_eq!(contract.event_count, 2);
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(rejected, false).unwrap();
+            }
+            assert_eq!(contract.get_amendment(rejected).unwrap().status, AmendmentStatus::Rejected);
+            assert_eq!(contract.get_event(event_id).unwrap().title, "Moon Landing");
+
+            // The submitter revises the event before the second amendment passes
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Updated description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(superseded, true).unwrap();
+            }
+            assert_eq!(contract.get_amendment(superseded).unwrap().status, AmendmentStatus::Superseded);
+            assert_eq!(contract.get_event(event_id).unwrap().description, "Updated description");
         }
 
         #[ink::test]
-        fn vote_works() {
+        fn submit_event_validates_evidence_by_kind() {
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event first
+
+            let cited = |kind: EvidenceKind, citation: &str| Evidence {
+                kind,
+                ..evidence(citation)
+            };
+            let submit = |contract: &mut HistoryProtocol, source: Evidence| {
+                contract.submit_event(
+                    String::from("Moon Landing"),
+                    19690720u64,
+                    String::from("Description"),
+                    vec![source],
+                    vec![String::from("Space")]
+                )
+            };
+
+            for (source, error) in [
+                (cited(EvidenceKind::Url, "nasa.gov/apollo11"), Error::InvalidUrl),
+                (cited(EvidenceKind::Url, "https://"), Error::InvalidUrl),
+                (cited(EvidenceKind::Isbn, "978-0-306-40615-8"), Error::InvalidIsbn),
+                (cited(EvidenceKind::Isbn, "12345"), Error::InvalidIsbn),
+                (cited(EvidenceKind::Doi, "doi:10.1000/xyz"), Error::InvalidDoi),
+                (cited(EvidenceKind::Doi, "10.1000"), Error::InvalidDoi),
+                (cited(EvidenceKind::IpfsCid, "QmShort"), Error::InvalidCid),
+                (cited(EvidenceKind::IpfsCid, "bAFY"), Error::InvalidCid),
+            ] {
+                assert_eq!(submit(&mut contract, source), Err(error));
+            }
+            assert_eq!(contract.event_count, 0);
+
+            for source in [
+                cited(EvidenceKind::Url, "https://nasa.gov/apollo11"),
+                cited(EvidenceKind::Isbn, "978-0-306-40615-7"),
+                cited(EvidenceKind::Isbn, "0-8044-2957-X"),
+                cited(EvidenceKind::Doi, "10.1000/xyz123"),
+                cited(EvidenceKind::IpfsCid, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                cited(EvidenceKind::IpfsCid, "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+                cited(EvidenceKind::PrimaryDocument, "Apollo 11 flight plan, NASA, 1969"),
+            ] {
+                assert!(submit(&mut contract, source).is_ok());
+            }
+
+            // Hashes and archived copies are stored as given
+            let hashed = Evidence {
+                content_hash: Some([7u8; 32]),
+                archived_copy: Some(String::from("https://web.archive.org/web/1999/nasa.gov")),
+                ..url("https://nasa.gov/apollo11")
+            };
+            let event_id = submit(&mut contract, hashed.clone()).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_sources, vec![hashed]);
+
+            let empty_copy = Evidence {
+                archived_copy: Some(String::new()),
+                ..url("https://nasa.gov/apollo11")
+            };
+            assert_eq!(submit(&mut contract, empty_copy), Err(Error::EmptyEvidence));
+        }
+
+        #[ink::test]
+        fn evidence_ratings_feed_quality_score() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence1"), evidence("evidence2")],
+                vec![String::from("Space")]
+            ).unwrap();
+
+            assert_eq!(contract.rate_evidence(event_id, 0, EvidenceRating::Credible), Err(Error::NotVoted));
+            contract.vote(event_id, true).unwrap();
+            assert_eq!(contract.rate_evidence(event_id, 2, EvidenceRating::Credible), Err(Error::EvidenceNotFound));
+            assert_eq!(contract.rate_evidence(999, 0, EvidenceRating::Credible), Err(Error::EventNotFound));
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, None);
+
+            contract.rate_evidence(event_id, 0, EvidenceRating::Credible).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, true).unwrap();
+            contract.rate_evidence(event_id, 0, EvidenceRating::Weak).unwrap();
+            // Only the rated source counts: (100 + 50) / 2
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(75));
+
+            contract.rate_evidence(event_id, 1, EvidenceRating::Fabricated).unwrap();
+            // (75 + 0) / 2
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(37));
+
+            // Rating again replaces the earlier rating
+            contract.rate_evidence(event_id, 0, EvidenceRating::Credible).unwrap();
+            assert_eq!(contract.get_evidence_rating(event_id, 0, accounts.bob), Some(EvidenceRating::Credible));
+            assert_eq!(contract.get_evidence_tallies(event_id), vec![
+                EvidenceTally { credible: 2, weak: 0, fabricated: 0 },
+                EvidenceTally { credible: 0, weak: 0, fabricated: 1 },
+            ]);
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(50));
+
+            // Dropping the fabricated source in a revision drops its rating from the score
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Description"),
+                vec![evidence("evidence1")],
+                vec![String::from("Space")]
+            ).unwrap();
+            for voter in [accounts.alice, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(100));
+        }
+
+        #[ink::test]
+        fn anyone_can_add_evidence_with_cooldown() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_config(Config {
+                evidence_cooldown: 1000,
+                ..Default::default()
+            }).unwrap();
+
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                vec![evidence("evidence1")],
+                vec![String::from("Space")]
             ).unwrap();
+            contract.vote(event_id, true).unwrap();
 
-            // Vote on the event
-            let result = contract.vote(event_id, true);
-            assert!(result.is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.add_evidence(event_id, evidence("evidence1"), false), Err(Error::DuplicateEvidence));
+            assert_eq!(contract.add_evidence(event_id, url("nasa.gov"), false), Err(Error::InvalidUrl));
+            assert_eq!(contract.add_evidence(999, evidence("evidence2"), false), Err(Error::EventNotFound));
 
-            // Verify vote was recorded
-            let caller = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice;
-            assert!(contract.has_voted(event_id, caller));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(contract.add_evidence(event_id, evidence("evidence2"), false), Ok(1));
+            assert_eq!(contract.add_evidence(event_id, evidence("evidence3"), false), Err(Error::EvidenceCooldown));
 
-            // Verify vote counts updated
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.support_votes, 1);
-            assert_eq!(event.challenge_votes, 0);
-            assert_eq!(event.consensus_score, 100);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.evidence_sources, vec![evidence("evidence1"), evidence("evidence2")]);
+            assert_eq!(event.revision, 1);
+            // A minor addition keeps earlier votes current
+            assert!(!contract.is_vote_outdated(event_id, accounts.alice));
+
+            let contributions = contract.get_evidence_contributions(event_id);
+            assert_eq!(contributions.len(), 1);
+            assert_eq!(contributions[0].contributor, accounts.bob);
+            assert_eq!(contributions[0].timestamp, 5000);
+            assert_eq!(contract.get_event_revisions(event_id, 0, 10)[1].author, accounts.bob);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let added = match <Event as scale::Decode>::decode(&mut &emitted_events.last().unwrap().data[..]) {
+                Ok(Event::EvidenceAdded(event)) => event,
+                _ => panic!("expected EvidenceAdded"),
+            };
+            assert_eq!(added.event_id, event_id);
+            assert_eq!(added.evidence_index, 1);
         }
 
         #[ink::test]
-        fn vote_prevents_duplicate_voting() {
+        fn significant_evidence_outdates_votes() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                vec![evidence("evidence1")],
+                vec![String::from("Space")]
             ).unwrap();
+            contract.vote(event_id, true).unwrap();
 
-            // First vote should succeed
-            let result1 = contract.vote(event_id, true);
-            assert!(result1.is_ok());
-
-            // Second vote from same user should fail
-            let result2 = contract.vote(event_id, false);
-            assert_eq!(result2, Err(Error::AlreadyVoted));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.add_evidence(event_id, evidence("evidence2"), true), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.grant_role(accounts.bob, Role::Verifier).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.add_evidence(event_id, evidence("evidence2"), true).unwrap();
 
-            // Verify only one vote was counted
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.support_votes, 1);
-            assert_eq!(event.challenge_votes, 0);
+            assert!(contract.is_vote_outdated(event_id, accounts.alice));
+            assert_eq!(contract.get_event(event_id).unwrap().substantive_revision, 1);
         }
 
         #[ink::test]
-        fn vote_fails_on_nonexistent_event() {
+        fn paged_queries_stop_at_byte_budget() {
             let mut contract = HistoryProtocol::new();
-            
-            // Try to vote on non-existent event
-            let result = contract.vote(999, true);
-            assert_eq!(result, Err(Error::EventNotFound));
+
+            let description = "x".repeat(4_000);
+            let count = (MAX_PAGE_BYTES / description.len()) as u64 + 2;
+            for _ in 0..count {
+                contract.submit_event(
+                    String::from("Event"),
+                    1000u64,
+                    description.clone(),
+                    vec![evidence("evidence")],
+                    vec![String::from("Science")]
+                ).unwrap();
+            }
+
+            // Walk the timeline; every page fits the budget and no event is skipped
+            let mut seen = Vec::new();
+            let mut cursor = Some(0);
+            while let Some(position) = cursor {
+                let page = contract.get_events_by_timeline_paged(Timeline::Pending, position, MAX_PAGE_SIZE);
+                assert!(!page.events.is_empty());
+                assert!(page.events.iter().map(scale::Encode::encoded_size).sum::<usize>() <= MAX_PAGE_BYTES);
+                assert!((page.events.len() as u64) < count);
+                seen.extend(page.events.iter().map(|event| event.id));
+                cursor = page.next_cursor;
+            }
+            assert_eq!(seen, (1..=count).collect::<Vec<_>>());
+
+            // Exports follow the same budget
+            let exported = contract.export_events(1, MAX_PAGE_SIZE);
+            assert!(!exported.is_empty());
+            assert!((exported.len() as u64) < count);
+            assert!(exported.iter().map(scale::Encode::encoded_size).sum::<usize>() <= MAX_PAGE_BYTES);
         }
 
         #[ink::test]
-        fn has_voted_returns_false_for_new_voter() {
-            let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+        fn unlock_keeps_stake_of_active_votes() {
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config { conviction_lock_period: 1000, ..Default::default() },
+                VotingMode::TokenWeighted,
+            ).unwrap();
+
             let event_id = contract.submit_event(
                 String::from("Test Event"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
+                vec![evidence("evidence")],
                 vec![String::from("Science")]
             ).unwrap();
 
-            let caller = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().alice;
-            assert!(!contract.has_voted(event_id, caller));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 300);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            contract.vote_with_conviction(event_id, true, Conviction::Locked1x).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            // The lock has expired, but the vote still holds its stake
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(contract.unlock(), Ok(0));
+            assert_eq!(contract.votes.get((event_id, accounts.bob)).unwrap().stake, 300);
+            assert_eq!(contract.get_event(event_id).unwrap().support_votes, 3000);
+
+            // Retracting returns it straight away instead of parking it in a lock
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            contract.retract_vote(event_id).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(balance_after - balance_before, 300);
+            assert!(contract.get_stake_locks(accounts.bob).is_empty());
         }
 
         #[ink::test]
-        fn consensus_score_calculation_works() {
-            let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+        fn settle_deposit_refunds_pending_events_after_timeout() {
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 100,
+                review_window: 1000,
+                pending_timeout: 5000,
+                ..Default::default()
+            }).unwrap();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 100);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
             let event_id = contract.submit_event(
                 String::from("Test Event"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
+                vec![evidence("evidence")],
                 vec![String::from("Science")]
             ).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
 
-            // Vote with support
-            contract.vote(event_id, true).unwrap();
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.consensus_score, 100); // 1/1 = 100%
+            // Nobody votes; the deposit stays held until the timeout
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4999);
+            assert_eq!(contract.settle_deposit(event_id), Err(Error::EventStillPending));
 
-            // Change caller and vote with challenge
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(
-                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>().bob
-            );
-            contract.vote(event_id, false).unwrap();
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.consensus_score, 50); // 1/2 = 50%
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+            contract.settle_deposit(event_id).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve).unwrap();
+
+            assert_eq!(balance_after - balance_before, 100);
+            assert_eq!(contract.get_deposit(event_id).unwrap().status, DepositStatus::Refunded);
+            assert_eq!(contract.get_event(event_id).unwrap().timeline, Timeline::Pending);
         }
 
         #[ink::test]
-        fn timeline_movement_to_canonical_works() {
+        fn pending_timeout_must_cover_review_window() {
+            let result = HistoryProtocol::with_config(Config {
+                review_window: 1000,
+                pending_timeout: 999,
+                ..Default::default()
+            });
+            assert!(matches!(result, Err(Error::InvalidConfig)));
+        }
+
+        #[ink::test]
+        fn maximum_size_event_fits_in_a_storage_cell() {
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+
+            // Every field at its limit; sources and tags differ in their first byte
+            let filled = |first: u8, len: usize| {
+                let mut text = String::from(char::from(first));
+                text.push_str(&"x".repeat(len - 1));
+                text
+            };
+            let evidence_sources: Vec<Evidence> = (0..MAX_EVIDENCE_SOURCES as u8)
+                .map(|i| Evidence {
+                    kind: EvidenceKind::Archive,
+                    citation: filled(b'a' + i, MAX_EVIDENCE_LENGTH),
+                    content_hash: Some([i; 32]),
+                    archived_copy: Some(filled(b'a' + i, MAX_EVIDENCE_LENGTH)),
+                })
+                .collect();
+            let tags: Vec<String> = (0..MAX_TAGS as u8)
+                .map(|i| filled(b'a' + i, MAX_TAG_LENGTH))
+                .collect();
+
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                filled(b't', MAX_TITLE_LENGTH),
                 1000u64,
-                String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                filled(b'd', MAX_DESCRIPTION_LENGTH),
+                evidence_sources,
+                tags
             ).unwrap();
 
-            // Initial timeline should be Disputed
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.timeline, Timeline::Disputed);
+            // Reading it back goes through the (smaller) off-chain storage buffer
+            let event = contract.get_event(event_id).unwrap();
+            assert!(scale::Encode::encoded_size(&event) <= 9 * 1024);
+        }
 
-            // Add 3 support votes to reach 75%+ consensus
+        #[ink::test]
+        fn imported_votes_carry_no_stake() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            contract.vote(event_id, true).unwrap();
-            
+            let mut source = HistoryProtocol::with_voting_mode(
+                Config::default(),
+                VotingMode::TokenWeighted,
+            ).unwrap();
+
+            let event_id = source.submit_event(
+                String::from("Test Event"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
+            ).unwrap();
+
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            contract.vote(event_id, true).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            contract.vote(event_id, true).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            source.vote_with_conviction(event_id, true, Conviction::Locked2x).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            let exported_events = source.export_events(1, 10);
+            let exported_votes = source.export_votes(event_id, 0, 10);
+            assert_eq!(exported_votes[0].stake, 500);
 
-            // Should move to Canonical (3/3 = 100%)
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.timeline, Timeline::Canonical);
-            assert_eq!(event.consensus_score, 100);
+            // The new deployment holds other users' funds
+            let target_id = AccountId::from([0x42; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(target_id);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(target_id, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let mut target = HistoryProtocol::with_voting_mode(
+                Config::default(),
+                VotingMode::TokenWeighted,
+            ).unwrap();
+            target.import_events(exported_events).unwrap();
+            target.import_votes(exported_votes).unwrap();
+
+            let vote = target.votes.get((event_id, accounts.bob)).unwrap();
+            assert_eq!(vote.stake, 0);
+            assert_eq!(vote.locked_until, vote.timestamp);
+            assert_eq!(vote.weight, 500 * 20);
+
+            // Retracting removes the weight but pays nothing out of the new contract
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            target.retract_vote(event_id).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(balance_after, balance_before);
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(target_id), Ok(1000));
+            assert!(target.get_stake_locks(accounts.bob).is_empty());
+            assert_eq!(target.get_event(event_id).unwrap().support_votes, 0);
         }
 
         #[ink::test]
-        fn timeline_movement_to_alternative_works() {
+        fn retracting_moves_last_voter_into_freed_slot() {
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+
             let event_id = contract.submit_event(
                 String::from("Test Event"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
+                vec![evidence("evidence")],
                 vec![String::from("Science")]
             ).unwrap();
 
-            // Add 3 challenge votes to reach 25% or less consensus
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            contract.vote(event_id, false).unwrap();
-            
+            for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote(event_id, true).unwrap();
+            }
+
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            contract.vote(event_id, false).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            contract.vote(event_id, false).unwrap();
+            contract.retract_vote(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            contract.retract_vote(event_id).unwrap();
 
-            // Should move to Alternative (0/3 = 0%)
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.timeline, Timeline::Alternative);
-            assert_eq!(event.consensus_score, 0);
+            let voters: Vec<AccountId> = contract.export_votes(event_id, 0, 10)
+                .into_iter()
+                .map(|vote| vote.voter)
+                .collect();
+            assert_eq!(voters, vec![accounts.alice, accounts.charlie]);
+
+            // A retracted voter can vote again and is appended
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote(event_id, false).unwrap();
+            assert_eq!(contract.export_votes(event_id, 2, 10)[0].voter, accounts.bob);
+            assert!(contract.export_votes(event_id, 3, 10).is_empty());
         }
 
         #[ink::test]
-        fn timeline_stays_disputed_for_mixed_votes() {
+        fn minor_edits_are_limited_to_small_text_changes() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+
+            let description = String::from("Apollo 11 landed in the Sea of Tranquility in 1969");
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                String::from("Moon Landing"),
                 1000u64,
-                String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                description.clone(),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
 
-            // Add mixed votes to keep in Disputed range (26-74%)
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("The footage was staged in a studio"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::SubstantiveEdit));
+
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                description.clone(),
+                vec![evidence("evidence"), url("https://nasa.gov")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::SubstantiveEdit));
+
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                description.clone(),
+                vec![evidence("evidence")],
+                vec![String::from("Technology")]
+            );
+            assert_eq!(result, Err(Error::SubstantiveEdit));
+
+            // Inserting a few words in the middle is still a correction
+            contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Apollo 11 safely landed in the Sea of Tranquility in 1969"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().substantive_revision, 0);
+        }
+
+        #[ink::test]
+        fn revisions_are_charged_per_stored_byte() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            contract.vote(event_id, true).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            contract.vote(event_id, false).unwrap();
+            let mut contract = HistoryProtocol::with_config(Config {
+                deposit_per_byte: 2,
+                ..Default::default()
+            }).unwrap();
 
-            // Should stay in Disputed (1/2 = 50%)
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.timeline, Timeline::Disputed);
-            assert_eq!(event.consensus_score, 50);
+            let content = (String::from("Description"), vec![evidence("evidence")], vec![String::from("Space")]);
+            let required = contract.estimate_deposit(
+                String::from("Moon Landnig"),
+                content.0.clone(),
+                content.1.clone(),
+                content.2.clone()
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(required);
+            let event_id = contract.submit_event(
+                String::from("Moon Landnig"),
+                1000u64,
+                content.0.clone(),
+                content.1.clone(),
+                content.2.clone()
+            ).unwrap();
+
+            // The first revision also stores a snapshot of the submitted content
+            let event = contract.get_event(event_id).unwrap();
+            let revision = EventRevision {
+                revision: 1,
+                title: String::from("Moon Landing"),
+                description: content.0.clone(),
+                evidence_sources: content.1.clone(),
+                tags: content.2.clone(),
+                author: accounts.alice,
+                timestamp: 0,
+                substantive: false,
+            };
+            let charge = 2 * (scale::Encode::encoded_size(&revision)
+                + scale::Encode::encoded_size(&HistoryProtocol::original_revision(&event))) as Balance;
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(charge - 1);
+            let result = contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                content.0.clone(),
+                content.1.clone(),
+                content.2.clone()
+            );
+            assert_eq!(result, Err(Error::InsufficientDeposit));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(charge);
+            contract.propose_revision(
+                event_id,
+                String::from("Moon Landing"),
+                content.0.clone(),
+                content.1.clone(),
+                content.2.clone()
+            ).unwrap();
+            assert_eq!(contract.get_deposit(event_id).unwrap().amount, required + charge);
+            assert_eq!(contract.event_revisions.get((event_id, 1)), Some(revision));
         }
 
         #[ink::test]
-        fn get_event_returns_existing_event() {
+        fn event_revisions_are_paged() {
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
-            let title = String::from("Test Event");
-            let date = 1000u64;
-            let description = String::from("Test Description");
-            let evidence = vec![String::from("evidence1")];
-            let tags = vec![String::from("Science")];
-            
+
             let event_id = contract.submit_event(
-                title.clone(),
-                date,
-                description.clone(),
-                evidence.clone(),
-                tags.clone()
+                String::from("Moon Landing 0"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
+            for n in 1..=4 {
+                contract.propose_revision(
+                    event_id,
+                    format!("Moon Landing {}", n),
+                    String::from("Description"),
+                    vec![evidence("evidence")],
+                    vec![String::from("Space")]
+                ).unwrap();
+            }
 
-            // Retrieve the event
-            let retrieved_event = contract.get_event(event_id);
-            assert!(retrieved_event.is_some());
-            
-            let event = retrieved_event.unwrap();
-            assert_eq!(event.id, event_id);
-            assert_eq!(event.title, title);
-            assert_eq!(event.date, date);
-            assert_eq!(event.description, description);
-            assert_eq!(event.evidence_sources, evidence);
-            assert_eq!(event.tags, tags);
+            let page = contract.get_event_revisions(event_id, 0, 2);
+            assert_eq!(page.iter().map(|r| r.revision).collect::<Vec<_>>(), vec![0, 1]);
+            let page = contract.get_event_revisions(event_id, 2, 10);
+            assert_eq!(page.iter().map(|r| r.revision).collect::<Vec<_>>(), vec![2, 3, 4]);
+            assert_eq!(page[2].title, "Moon Landing 4");
+            assert!(contract.get_event_revisions(event_id, 5, 10).is_empty());
+            assert!(contract.get_event_revisions(99, 0, 10).is_empty());
         }
 
         #[ink::test]
-        fn get_event_returns_none_for_nonexistent_event() {
-            let contract = HistoryProtocol::new();
-            
-            // Try to retrieve non-existent event
-            let retrieved_event = contract.get_event(999);
-            assert!(retrieved_event.is_none());
+        fn event_amendments_are_paged_by_size() {
+            let mut contract = HistoryProtocol::new();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            for n in 0..3 {
+                contract.propose_amendment(
+                    event_id,
+                    format!("Moon Landing {}", n),
+                    "x".repeat(MAX_DESCRIPTION_LENGTH),
+                    vec![evidence("evidence")],
+                    vec![String::from("Space")]
+                ).unwrap();
+            }
+
+            // Three full-length amendments do not fit in one page
+            let page = contract.get_event_amendments(event_id, 0, 10);
+            assert_eq!(page.len(), 2);
+            assert_eq!(page[1].title, "Moon Landing 1");
+            let page = contract.get_event_amendments(event_id, 2, 10);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].title, "Moon Landing 2");
+            assert!(contract.get_event_amendments(event_id, 3, 10).is_empty());
+            assert!(contract.get_event_amendments(99, 0, 10).is_empty());
         }
 
         #[ink::test]
-        fn get_events_by_timeline_returns_disputed_events() {
-            let mut contract = HistoryProtocol::new();
-            
-            // Submit multiple events (all start in Disputed timeline)
-            let event_id1 = contract.submit_event(
-                String::from("Event 1"),
-                1000u64,
-                String::from("Description 1"),
-                vec![String::from("evidence1")],
-                vec![String::from("Science")]
+        fn amendment_votes_are_weighted_by_stake() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config::default(),
+                VotingMode::TokenWeighted,
             ).unwrap();
-            
-            let event_id2 = contract.submit_event(
-                String::from("Event 2"),
-                2000u64,
-                String::from("Description 2"),
-                vec![String::from("evidence2")],
-                vec![String::from("Technology")]
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 10_000);
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
+                1000u64,
+                String::from("Apollo 11 landed on 20 July 1969"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
 
-            // Retrieve events from Disputed timeline
-            let disputed_events = contract.get_events_by_timeline(Timeline::Disputed);
-            assert_eq!(disputed_events.len(), 2);
-            
-            // Verify both events are present
-            assert!(disputed_events.iter().any(|e| e.id == event_id1));
-            assert!(disputed_events.iter().any(|e| e.id == event_id2));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("The landing footage was staged in a studio"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(contract.vote_amendment(amendment_id, true), Err(Error::InsufficientStake));
+
+            // Three small stakes in favour don't outweigh one large stake against
+            for voter in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(10);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(contract.reclaim_amendment_stake(amendment_id), Err(Error::AmendmentStillOpen));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.vote_amendment(amendment_id, false).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            let amendment = contract.get_amendment(amendment_id).unwrap();
+            assert_eq!(amendment.status, AmendmentStatus::Rejected);
+            assert_eq!((amendment.support_votes, amendment.reject_votes, amendment.voter_count), (20, 1000, 3));
+            assert_eq!(contract.get_event(event_id).unwrap().revision, 0);
+
+            // Stakes come back once the amendment is closed
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django).unwrap();
+            assert_eq!(contract.reclaim_amendment_stake(amendment_id), Ok(1000));
+            assert_eq!(contract.reclaim_amendment_stake(amendment_id), Ok(0));
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django).unwrap();
+            assert_eq!(balance_after, balance_before + 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.reclaim_amendment_stake(amendment_id), Err(Error::NotVoted));
         }
 
         #[ink::test]
-        fn get_events_by_timeline_returns_canonical_events() {
-            let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+        fn quadratic_amendment_votes_spend_credits() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config {
+                    credits_per_period: 1,
+                    ..Default::default()
+                },
+                VotingMode::Quadratic,
+            ).unwrap();
+
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
-
-            // Vote to move to Canonical timeline (3 support votes = 100%)
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            contract.vote(event_id, true).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            contract.vote(event_id, true).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
             contract.vote(event_id, true).unwrap();
 
-            // Retrieve events from Canonical timeline
-            let canonical_events = contract.get_events_by_timeline(Timeline::Canonical);
-            assert_eq!(canonical_events.len(), 1);
-            assert_eq!(canonical_events[0].id, event_id);
-            assert_eq!(canonical_events[0].timeline, Timeline::Canonical);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing 1969"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert_eq!(contract.vote_amendment(amendment_id, true), Err(Error::InsufficientCredits));
 
-            // Disputed timeline should now be empty
-            let disputed_events = contract.get_events_by_timeline(Timeline::Disputed);
-            assert_eq!(disputed_events.len(), 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.vote_amendment(amendment_id, true).unwrap();
+            assert_eq!(contract.get_vote_credits(accounts.bob), 0);
+            let spends = contract.get_credit_spends(accounts.bob, 0, 10);
+            assert_eq!(spends.len(), 1);
+            assert_eq!(spends[0].amendment_id, Some(amendment_id));
         }
 
         #[ink::test]
-        fn get_events_by_timeline_returns_alternative_events() {
+        fn minor_amendment_keeps_votes_current() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            // Submit an event
+
             let event_id = contract.submit_event(
-                String::from("Test Event"),
+                String::from("Moon Landnig"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![String::from("Science")]
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
+            contract.vote(event_id, true).unwrap();
 
-            // Vote to move to Alternative timeline (3 challenge votes = 0%)
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            contract.vote(event_id, false).unwrap();
-            
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            contract.vote(event_id, false).unwrap();
-            
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            contract.vote(event_id, false).unwrap();
-
-            // Retrieve events from Alternative timeline
-            let alternative_events = contract.get_events_by_timeline(Timeline::Alternative);
-            assert_eq!(alternative_events.len(), 1);
-            assert_eq!(alternative_events[0].id, event_id);
-            assert_eq!(alternative_events[0].timeline, Timeline::Alternative);
-
-            // Disputed timeline should now be empty
-            let disputed_events = contract.get_events_by_timeline(Timeline::Disputed);
-            assert_eq!(disputed_events.len(), 0);
-        }
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            assert!(!contract.get_amendment(amendment_id).unwrap().substantive);
 
-        #[ink::test]
-        fn get_events_by_timeline_returns_empty_for_empty_timeline() {
-            let contract = HistoryProtocol::new();
-            
-            // All timelines should be empty initially
-            let canonical_events = contract.get_events_by_timeline(Timeline::Canonical);
-            assert_eq!(canonical_events.len(), 0);
-            
-            let disputed_events = contract.get_events_by_timeline(Timeline::Disputed);
-            assert_eq!(disputed_events.len(), 0);
-            
-            let alternative_events = contract.get_events_by_timeline(Timeline::Alternative);
-            assert_eq!(alternative_events.len(), 0);
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            assert_eq!(contract.get_event(event_id).unwrap().title, "Moon Landing");
+            assert!(!contract.is_vote_outdated(event_id, accounts.alice));
         }
 
         #[ink::test]
-        fn get_user_events_returns_user_submitted_events() {
-            let mut contract = HistoryProtocol::new();
-            
+        fn amendment_deposit_is_settled_after_close() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            // Alice submits two events
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            let event_id1 = contract.submit_event(
-                String::from("Alice Event 1"),
+            let mut contract = HistoryProtocol::with_config(Config {
+                submission_deposit: 1000,
+                slash_percent: 50,
+                review_window: 1000,
+                pending_timeout: 5000,
+                ..Default::default()
+            }).unwrap();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 10_000);
+            contract.set_treasury(accounts.frank).unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
                 1000u64,
-                String::from("Description 1"),
-                vec![String::from("evidence1")],
-                vec![String::from("Science")]
-            ).unwrap();
-            
-            let event_id2 = contract.submit_event(
-                String::from("Alice Event 2"),
-                2000u64,
-                String::from("Description 2"),
-                vec![String::from("evidence2")],
-                vec![String::from("Technology")]
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
 
-            // Bob submits one event
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            let event_id3 = contract.submit_event(
-                String::from("Bob Event"),
-                3000u64,
-                String::from("Description 3"),
-                vec![String::from("evidence3")],
-                vec![String::from("Politics")]
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(999);
+            let result = contract.propose_amendment(
+                event_id,
+                String::from("Moon Hoax"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            );
+            assert_eq!(result, Err(Error::InsufficientDeposit));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let rejected = contract.propose_amendment(
+                event_id,
+                String::from("Moon Hoax"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
             ).unwrap();
+            let expired = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing 1969"),
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(contract.get_amendment(rejected).unwrap().deposit, 1000);
 
-            // Retrieve Alice's events
-            let alice_events = contract.get_user_events(accounts.alice);
-            assert_eq!(alice_events.len(), 2);
-            assert!(alice_events.iter().any(|e| e.id == event_id1));
-            assert!(alice_events.iter().any(|e| e.id == event_id2));
-            assert!(!alice_events.iter().any(|e| e.id == event_id3));
+            // A rejected proposal loses `slash_percent` of its deposit to the treasury
+            assert_eq!(contract.settle_amendment_deposit(rejected), Err(Error::AmendmentStillOpen));
+            let bob_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            let treasury_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            for voter in [accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(rejected, false).unwrap();
+            }
+            // Closing the amendment pays nothing out until someone settles it
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap(),
+                bob_before
+            );
+            contract.settle_amendment_deposit(rejected).unwrap();
+            assert_eq!(contract.get_amendment(rejected).unwrap().deposit_status, DepositStatus::Slashed);
+            assert_eq!(contract.settle_amendment_deposit(rejected), Err(Error::DepositAlreadySettled));
+            let bob_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            let treasury_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            assert_eq!(bob_after, bob_before + 500);
+            assert_eq!(treasury_after, treasury_before + 500);
 
-            // Retrieve Bob's events
-            let bob_events = contract.get_user_events(accounts.bob);
-            assert_eq!(bob_events.len(), 1);
-            assert_eq!(bob_events[0].id, event_id3);
+            // An amendment nobody decides on is refunded in full after the timeout
+            assert_eq!(contract.expire_amendment(expired), Err(Error::AmendmentStillOpen));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            contract.expire_amendment(expired).unwrap();
+            assert_eq!(contract.get_amendment(expired).unwrap().status, AmendmentStatus::Expired);
+            contract.settle_amendment_deposit(expired).unwrap();
+            assert_eq!(contract.get_amendment(expired).unwrap().deposit_status, DepositStatus::Refunded);
+            let bob_final = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_final, bob_after + 1000);
+            assert_eq!(contract.expire_amendment(expired), Err(Error::AmendmentClosed));
+            assert_eq!(contract.expire_amendment(rejected), Err(Error::AmendmentClosed));
         }
 
         #[ink::test]
-        fn get_user_events_returns_empty_for_user_with_no_events() {
-            let contract = HistoryProtocol::new();
-            
+        fn evidence_ratings_are_weighted_by_votes() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            // Charlie has not submitted any events
-            let charlie_events = contract.get_user_events(accounts.charlie);
-            assert_eq!(charlie_events.len(), 0);
-        }
-
-        #[ink::test]
-        fn submit_event_with_tags_works() {
-            let mut contract = HistoryProtocol::new();
-            
-            let tags = vec![
-                String::from("Science"),
-                String::from("Technology"),
-                String::from("Space")
-            ];
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_voting_mode(
+                Config::default(),
+                VotingMode::TokenWeighted,
+            ).unwrap();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 10_000);
 
-            let result = contract.submit_event(
-                String::from("Test Event"),
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                tags.clone()
-            );
+                vec![evidence("evidence")],
+                vec![String::from("Space")]
+            ).unwrap();
 
-            assert!(result.is_ok());
-            let event_id = result.unwrap();
-            
-            let event = contract.events.get(event_id).unwrap();
-            assert_eq!(event.tags, tags);
+            // One large stake outweighs two small ones
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(800);
+            contract.vote(event_id, true).unwrap();
+            contract.rate_evidence(event_id, 0, EvidenceRating::Credible).unwrap();
+            for voter in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+                contract.vote(event_id, false).unwrap();
+                contract.rate_evidence(event_id, 0, EvidenceRating::Fabricated).unwrap();
+            }
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(contract.get_evidence_tallies(event_id), vec![
+                EvidenceTally { credible: 800, weak: 0, fabricated: 200 },
+            ]);
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(80));
+
+            // Retracting the vote takes its ratings with it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.retract_vote(event_id).unwrap();
+            assert_eq!(contract.get_evidence_rating(event_id, 0, accounts.alice), None);
+            assert_eq!(contract.get_event(event_id).unwrap().evidence_quality_score, Some(0));
+            assert_eq!(contract.rate_evidence(event_id, 0, EvidenceRating::Credible), Err(Error::NotVoted));
         }
 
         #[ink::test]
-        fn submit_event_fails_with_empty_tags() {
-            let mut contract = HistoryProtocol::new();
-            
-            let result = contract.submit_event(
-                String::from("Test Event"),
+        fn evidence_deposit_is_slashed_for_fabricated_sources() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_config(Config {
+                evidence_deposit: 100,
+                evidence_cooldown: 0,
+                review_window: 1000,
+                slash_percent: 40,
+                ..Default::default()
+            }).unwrap();
+            let contract_id = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_id, 1000);
+            contract.set_treasury(accounts.frank).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                vec![]
-            );
+                vec![evidence("evidence1")],
+                vec![String::from("Space")]
+            ).unwrap();
+            contract.vote(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.add_evidence(event_id, evidence("evidence2"), false), Err(Error::InsufficientDeposit));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.add_evidence(event_id, evidence("fabricated"), false).unwrap();
+            contract.add_evidence(event_id, evidence("evidence2"), false).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.rate_evidence(event_id, 1, EvidenceRating::Fabricated).unwrap();
+            assert_eq!(contract.settle_evidence_deposit(event_id, 0), Err(Error::ReviewWindowOpen));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+
+            let bob_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            let treasury_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            contract.settle_evidence_deposit(event_id, 0).unwrap();
+            contract.settle_evidence_deposit(event_id, 1).unwrap();
+            let bob_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            let treasury_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank).unwrap();
+            assert_eq!(treasury_after - treasury_before, 40);
+            assert_eq!(bob_after - bob_before, 60 + 100);
 
-            assert_eq!(result, Err(Error::InvalidEventData));
+            let contributions = contract.get_evidence_contributions(event_id);
+            assert_eq!(contributions[0].deposit_status, DepositStatus::Slashed);
+            assert_eq!(contributions[1].deposit_status, DepositStatus::Refunded);
+            assert_eq!(contract.settle_evidence_deposit(event_id, 0), Err(Error::DepositAlreadySettled));
+            assert_eq!(contract.settle_evidence_deposit(event_id, 2), Err(Error::EvidenceNotFound));
         }
 
         #[ink::test]
-        fn submit_event_fails_with_too_many_tags() {
-            let mut contract = HistoryProtocol::new();
-            
-            let tags = vec![
-                String::from("Tag1"),
-                String::from("Tag2"),
-                String::from("Tag3"),
-                String::from("Tag4"),
-                String::from("Tag5"),
-                String::from("Tag6")
-            ];
+        fn added_evidence_does_not_supersede_amendments() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut contract = HistoryProtocol::with_config(Config {
+                evidence_cooldown: 0,
+                ..Default::default()
+            }).unwrap();
 
-            let result = contract.submit_event(
-                String::from("Test Event"),
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
                 1000u64,
                 String::from("Description"),
-                vec![String::from("evidence")],
-                tags
-            );
+                vec![evidence("evidence1")],
+                vec![String::from("Space")]
+            ).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Apollo 11 landed on 20 July 1969"),
+                vec![evidence("evidence1"), url("https://nasa.gov")],
+                vec![String::from("Space")]
+            ).unwrap();
+
+            // Another account appends a source while the vote is running
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.add_evidence(event_id, evidence("evidence2"), false).unwrap();
 
-            assert_eq!(result, Err(Error::InvalidEventData));
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            assert_eq!(contract.get_amendment(amendment_id).unwrap().status, AmendmentStatus::Applied);
+
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.description, "Apollo 11 landed on 20 July 1969");
+            assert_eq!(event.evidence_sources, vec![
+                evidence("evidence1"),
+                url("https://nasa.gov"),
+                evidence("evidence2"),
+            ]);
+            assert_eq!(event.revision, 2);
         }
 
         #[ink::test]
-        fn get_events_by_tag_returns_matching_events() {
+        fn curator_retags_are_recorded_as_revisions() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
             let mut contract = HistoryProtocol::new();
-            
-            // Submit events with different tags
-            let event_id1 = contract.submit_event(
-                String::from("Science Event"),
+            contract.grant_role(accounts.eve, Role::Curator).unwrap();
+
+            let event_id = contract.submit_event(
+                String::from("Moon Landing"),
                 1000u64,
-                String::from("Description 1"),
-                vec![String::from("evidence1")],
-                vec![String::from("Science"), String::from("Technology")]
-            ).unwrap();
-            
-            let event_id2 = contract.submit_event(
-                String::from("Politics Event"),
-                2000u64,
-                String::from("Description 2"),
-                vec![String::from("evidence2")],
-                vec![String::from("Politics")]
+                String::from("Description"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
             ).unwrap();
-            
-            let event_id3 = contract.submit_event(
-                String::from("Another Science Event"),
-                3000u64,
-                String::from("Description 3"),
-                vec![String::from("evidence3")],
-                vec![String::from("Science"), String::from("Space")]
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let amendment_id = contract.propose_amendment(
+                event_id,
+                String::from("Moon Landing"),
+                String::from("Apollo 11 landed on 20 July 1969"),
+                vec![evidence("evidence")],
+                vec![String::from("Science")]
             ).unwrap();
 
-            // Query events by "Science" tag
-            let science_events = contract.get_events_by_tag(String::from("Science"));
-            assert_eq!(science_events.len(), 2);
-            assert!(science_events.iter().any(|e| e.id == event_id1));
-            assert!(science_events.iter().any(|e| e.id == event_id3));
-            assert!(!science_events.iter().any(|e| e.id == event_id2));
+            let space = vec![String::from("Space")];
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.set_event_tags(event_id, space.clone()).unwrap();
 
-            // Query events by "Politics" tag
-            let politics_events = contract.get_events_by_tag(String::from("Politics"));
-            assert_eq!(politics_events.len(), 1);
-            assert_eq!(politics_events[0].id, event_id2);
+            // The submitted content stays revision 0
+            let revisions = contract.get_event_revisions(event_id, 0, 10);
+            assert_eq!(revisions.len(), 2);
+            assert_eq!(revisions[0].tags, vec![String::from("Science")]);
+            assert_eq!(revisions[0].author, accounts.alice);
+            assert_eq!(revisions[1].tags, space);
+            assert_eq!(revisions[1].author, accounts.eve);
+            assert!(!revisions[1].substantive);
+
+            // An amendment that leaves the tags alone keeps the curator's
+            for voter in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                contract.vote_amendment(amendment_id, true).unwrap();
+            }
+            assert_eq!(contract.get_amendment(amendment_id).unwrap().status, AmendmentStatus::Applied);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.description, "Apollo 11 landed on 20 July 1969");
+            assert_eq!(event.tags, space);
+            assert_eq!(event.revision, 2);
         }
     }
 }
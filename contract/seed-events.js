@@ -16,6 +16,16 @@ const path = require('path');
 
 const CONTRACT_ADDRESS = '5E1FvPudm4Lhb54HBE4Hhei6JpfH5b6bEoBVPhcTHzeLfG2q';
 
+// Converts a plain source string into the contract's Evidence struct
+function toEvidence(source) {
+  return {
+    kind: /^https?:\/\//.test(source) ? 'Url' : 'Archive',
+    citation: source,
+    contentHash: null,
+    archivedCopy: null,
+  };
+}
+
 // Test events from Space Race era (1957-1972)
 const TEST_EVENTS = [
   // CANONICAL EVENTS (will get high support votes)
@@ -261,7 +271,7 @@ async function seedEvents() {
               event.title,
               timestamp,
               event.description,
-              event.evidenceSources,
+              event.evidenceSources.map(toEvidence),
               event.tags
             )
             .signAndSend(alice, ({ status, dispatchError }) => {
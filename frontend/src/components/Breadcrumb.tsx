@@ -7,6 +7,7 @@ export function Breadcrumb() {
   const getBreadcrumbName = (segment: string) => {
     // Capitalize and format segment names
     if (segment === 'timeline') return 'Timeline'
+    if (segment === 'pending') return 'Pending'
     if (segment === 'canonical') return 'Canonical'
     if (segment === 'disputed') return 'Disputed'
     if (segment === 'alternative') return 'Alternative'
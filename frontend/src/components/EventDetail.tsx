@@ -144,6 +144,8 @@ export function EventDetail({ eventId, onClose, onVoteSuccess }: EventDetailProp
   const getTimelineColor = (timeline: Timeline) => {
     const timelineLower = timeline?.toLowerCase();
     switch (timelineLower) {
+      case 'pending':
+        return 'bg-blue-100 dark:bg-blue-900/30 border-blue-300 dark:border-blue-700';
       case 'canonical':
         return 'bg-green-100 dark:bg-green-900/30 border-green-300 dark:border-green-700';
       case 'disputed':
@@ -158,6 +160,8 @@ export function EventDetail({ eventId, onClose, onVoteSuccess }: EventDetailProp
   const getTimelineTextColor = (timeline: Timeline) => {
     const timelineLower = timeline?.toLowerCase();
     switch (timelineLower) {
+      case 'pending':
+        return '#1d4ed8'; // blue-700
       case 'canonical':
         return '#15803d'; // green-700
       case 'disputed':
@@ -310,9 +314,10 @@ export function EventDetail({ eventId, onClose, onVoteSuccess }: EventDetailProp
             {/* Timeline Movement Info */}
             <div className="mt-4 pt-4 border-t border-gray-300 dark:border-gray-600">
               <p className="text-xs text-gray-600 dark:text-gray-400">
-                {event.consensusScore >= 75 && '✓ This event has reached canonical status (≥75% support)'}
-                {event.consensusScore <= 25 && '✗ This event is considered an alternative interpretation (≤25% support)'}
-                {event.consensusScore > 25 && event.consensusScore < 75 && 
+                {event.timeline?.toLowerCase() === 'pending' && 'This event is pending until enough accounts have voted on it.'}
+                {event.timeline?.toLowerCase() !== 'pending' && event.consensusScore >= 75 && '✓ This event has reached canonical status (≥75% support)'}
+                {event.timeline?.toLowerCase() !== 'pending' && event.consensusScore <= 25 && '✗ This event is considered an alternative interpretation (≤25% support)'}
+                {event.timeline?.toLowerCase() !== 'pending' && event.consensusScore > 25 && event.consensusScore < 75 && 
                   `This event is currently disputed. It needs ${75 - event.consensusScore}% more support to become canonical, or ${event.consensusScore - 25}% more challenges to become alternative.`
                 }
               </p>
@@ -360,12 +365,12 @@ export function EventDetail({ eventId, onClose, onVoteSuccess }: EventDetailProp
                       <path d="M5 5a2 2 0 00-2 2v8a2 2 0 002 2h8a2 2 0 002-2v-3a1 1 0 10-2 0v3H5V7h3a1 1 0 000-2H5z" />
                     </svg>
                     <a
-                      href={source.startsWith('http') ? source : `https://${source}`}
+                      href={source.citation.startsWith('http') ? source.citation : `https://${source.citation}`}
                       target="_blank"
                       rel="noopener noreferrer"
                       className="text-blue-600 dark:text-blue-400 hover:text-blue-700 dark:hover:text-blue-300 hover:underline break-all"
                     >
-                      {source}
+                      {source.citation}
                     </a>
                   </li>
                 ))}
@@ -186,7 +186,7 @@ export const EventSubmission: React.FC = () => {
 
     try {
       // Import contract service dynamically to avoid circular dependencies
-      const { submitEvent } = await import('../services/contractService');
+      const { submitEvent, toEvidence } = await import('../services/contractService');
       const { web3Accounts } = await import('@polkadot/extension-dapp');
       
       // Get the full injected account
@@ -222,7 +222,7 @@ export const EventSubmission: React.FC = () => {
         formData.title.trim(),
         dateNumber,
         formData.description.trim(),
-        validSources,
+        validSources.map(toEvidence),
         formData.tags
       );
 
@@ -290,7 +290,7 @@ export const EventSubmission: React.FC = () => {
             Event Submitted Successfully!
           </h3>
           <p className="text-sm text-green-800 dark:text-green-400">
-            Your event has been added to the Disputed timeline.
+            Your event has been added to the Pending timeline until it reaches the voting quorum.
             {eventId && ` Event ID: ${eventId}`}
           </p>
         </div>
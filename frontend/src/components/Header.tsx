@@ -77,6 +77,16 @@ export function Header() {
           >
             Home
           </Link>
+          <Link
+            to="/timeline/pending"
+            className={`px-3 lg:px-4 py-2 rounded-lg font-medium transition-colors text-sm lg:text-base ${
+              isActive('/timeline/pending')
+                ? 'bg-blue-600 text-white'
+                : 'text-gray-700 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700'
+            }`}
+          >
+            Pending
+          </Link>
           <Link
             to="/timeline/canonical"
             className={`px-3 lg:px-4 py-2 rounded-lg font-medium transition-colors text-sm lg:text-base ${
@@ -134,6 +144,17 @@ export function Header() {
             >
               Home
             </Link>
+            <Link
+              to="/timeline/pending"
+              onClick={() => setMobileMenuOpen(false)}
+              className={`block px-4 py-2 rounded-lg font-medium transition-colors ${
+                isActive('/timeline/pending')
+                  ? 'bg-blue-600 text-white'
+                  : 'text-gray-700 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700'
+              }`}
+            >
+              Pending Timeline
+            </Link>
             <Link
               to="/timeline/canonical"
               onClick={() => setMobileMenuOpen(false)}
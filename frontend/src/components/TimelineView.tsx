@@ -37,7 +37,7 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
     const urlStartDate = params.get('startDate');
     const urlEndDate = params.get('endDate');
 
-    if (urlTab && ['all', 'canonical', 'disputed', 'alternative'].includes(urlTab)) {
+    if (urlTab && ['all', 'pending', 'canonical', 'disputed', 'alternative'].includes(urlTab)) {
       setActiveTab(urlTab);
     }
     if (urlStartDate) setStartDate(urlStartDate);
@@ -164,6 +164,8 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
   const getTimelineColor = (timeline: Timeline) => {
     const timelineLower = timeline?.toLowerCase();
     switch (timelineLower) {
+      case 'pending':
+        return 'bg-blue-100 dark:bg-blue-900/30 border-blue-300 dark:border-blue-700';
       case 'canonical':
         return 'bg-green-100 dark:bg-green-900/30 border-green-300 dark:border-green-700';
       case 'disputed':
@@ -178,6 +180,8 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
   const getTimelineTextColor = (timeline: Timeline) => {
     const timelineLower = timeline?.toLowerCase();
     switch (timelineLower) {
+      case 'pending':
+        return '#1d4ed8'; // blue-700
       case 'canonical':
         return '#15803d'; // green-700
       case 'disputed':
@@ -214,6 +218,22 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
             </span>
           </button>
           
+          <button
+            onClick={() => setActiveTab('pending')}
+            className={`
+              flex-1 py-4 px-6 text-center font-medium text-sm border-b-2 transition-colors
+              ${activeTab === 'pending'
+                ? 'border-blue-500 text-blue-600 dark:text-blue-400'
+                : 'border-transparent text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300 hover:border-gray-300 dark:hover:border-gray-600'
+              }
+            `}
+          >
+            <span className="flex items-center justify-center gap-2">
+              <span className="w-3 h-3 rounded-full bg-blue-500"></span>
+              Pending
+            </span>
+          </button>
+          
           <button
             onClick={() => setActiveTab('canonical')}
             className={`
@@ -273,6 +293,7 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
             </h3>
             <p className="text-sm text-gray-600 dark:text-gray-400">
               {activeTab === 'all' && 'View all historical events from all timelines in chronological order'}
+              {activeTab === 'pending' && 'New events waiting for enough votes to be placed on a timeline'}
               {activeTab === 'canonical' && 'Events with high community consensus (≥75% support)'}
               {activeTab === 'disputed' && 'Events currently under debate (26-74% support)'}
               {activeTab === 'alternative' && 'Alternative interpretations (≤25% support)'}
@@ -450,7 +471,7 @@ export function TimelineView({ onEventSelect, initialTimeline = 'all' }: Timelin
                 : `There are no events in the ${activeTab} timeline yet.`
               }
             </p>
-            {(activeTab === 'disputed' || activeTab === 'all') && (
+            {(activeTab === 'pending' || activeTab === 'all') && (
               <p className="text-sm text-gray-500 dark:text-gray-400">
                 Submit a new historical event to get started!
               </p>
@@ -24,6 +24,13 @@ export function TimelineVisualization({
   // Get color for timeline type
   const getTimelineColor = (timeline: Timeline) => {
     switch (timeline) {
+      case 'pending':
+        return {
+          bg: 'bg-blue-500',
+          border: 'border-blue-500',
+          text: 'text-blue-700 dark:text-blue-300',
+          hover: 'hover:bg-blue-600'
+        };
       case 'canonical':
         return {
           bg: 'bg-green-500',
@@ -92,6 +99,10 @@ export function TimelineVisualization({
 
       {/* Legend */}
       <div className="flex flex-wrap items-center gap-4 md:gap-6 mb-6 pb-4 border-b border-gray-200 dark:border-gray-700">
+        <div className="flex items-center gap-2">
+          <div className="w-4 h-4 rounded-full" style={{ backgroundColor: '#3b82f6' }}></div>
+          <span className="text-sm font-semibold" style={{ color: '#111827' }}>Pending (awaiting quorum)</span>
+        </div>
         <div className="flex items-center gap-2">
           <div className="w-4 h-4 rounded-full" style={{ backgroundColor: '#10b981' }}></div>
           <span className="text-sm font-semibold" style={{ color: '#111827' }}>Canonical (≥75%)</span>
@@ -158,6 +169,7 @@ export function TimelineVisualization({
                         className="ml-3 px-3 py-1 rounded-full text-xs font-semibold text-white shadow-sm flex-shrink-0"
                         style={{
                           backgroundColor: 
+                            event.timeline?.toLowerCase() === 'pending' ? '#3b82f6' : 
                             event.timeline?.toLowerCase() === 'canonical' ? '#10b981' : 
                             event.timeline?.toLowerCase() === 'disputed' ? '#f59e0b' : 
                             event.timeline?.toLowerCase() === 'alternative' ? '#ef4444' : '#6b7280'
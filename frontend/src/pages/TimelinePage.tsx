@@ -7,7 +7,7 @@ export function TimelinePage() {
   const { timelineType } = useParams<{ timelineType: string }>()
 
   // Validate timeline type and default to 'all' if invalid
-  const validTimelineTypes: TimelineViewType[] = ['all', 'canonical', 'disputed', 'alternative']
+  const validTimelineTypes: TimelineViewType[] = ['all', 'pending', 'canonical', 'disputed', 'alternative']
   const validatedTimelineType = validTimelineTypes.includes(timelineType as TimelineViewType)
     ? (timelineType as TimelineViewType)
     : 'all'
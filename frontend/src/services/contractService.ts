@@ -10,7 +10,7 @@ import { ContractPromise } from '@polkadot/api-contract';
 import { CONTRACT_CONFIG } from '../config/contract';
 // @ts-ignore - JSON import
 import contractMetadata from '../contracts/history_protocol.json';
-import type { HistoricalEvent, Timeline } from '../types';
+import type { Evidence, HistoricalEvent, Timeline } from '../types';
 import { retry, isNetworkError } from '../utils/retry';
 
 // Singleton instances
@@ -151,6 +151,24 @@ export interface TransactionResult {
   blockHash?: string;
 }
 
+/**
+ * Converts a plain source string entered in a form into a typed evidence source
+ * 
+ * Web links become `Url` evidence; anything else is cited as an `Archive` record.
+ * 
+ * @param source - URL or free-text citation
+ * @returns Evidence - Evidence source ready to submit
+ */
+export function toEvidence(source: string): Evidence {
+  const citation = source.trim();
+  return {
+    kind: /^https?:\/\//.test(citation) ? 'Url' : 'Archive',
+    citation,
+    contentHash: null,
+    archivedCopy: null,
+  };
+}
+
 /**
  * Submits a new historical event to the contract
  * 
@@ -158,7 +176,7 @@ export interface TransactionResult {
  * @param title - Event title
  * @param date - Unix timestamp of the event
  * @param description - Event description
- * @param evidenceSources - Array of evidence URLs/references
+ * @param evidenceSources - Array of typed evidence sources (see `toEvidence`)
  * @param tags - Array of category tags for the event
  * @returns Promise<TransactionResult> - Result containing event ID or error
  */
@@ -167,7 +185,7 @@ export async function submitEvent(
   title: string,
   date: number,
   description: string,
-  evidenceSources: string[],
+  evidenceSources: Evidence[],
   tags: string[]
 ): Promise<TransactionResult> {
   try {
@@ -413,7 +431,7 @@ export async function getEvent(eventId: number): Promise<HistoricalEvent | null>
 /**
  * Retrieves all events in a specific timeline
  * 
- * @param timeline - The timeline to query ('Canonical', 'Disputed', or 'Alternative')
+ * @param timeline - The timeline to query ('Pending', 'Canonical', 'Disputed', or 'Alternative')
  * @returns Promise<HistoricalEvent[]> - Array of events in the timeline
  */
 export async function getEventsByTimeline(timeline: Timeline): Promise<HistoricalEvent[]> {
@@ -582,7 +600,7 @@ export async function getEventsByTag(tag: string): Promise<HistoricalEvent[]> {
 }
 
 /**
- * Retrieves all events from all three timelines (canonical, disputed, alternative)
+ * Retrieves all events from all four timelines (pending, canonical, disputed, alternative)
  * and merges them into a single chronologically sorted array
  * 
  * @returns Promise<HistoricalEvent[]> - Array of all events sorted by date
@@ -591,20 +609,21 @@ export async function getAllEvents(): Promise<HistoricalEvent[]> {
   try {
     return await retry(
       async () => {
-        // Fetch events from all three timelines in parallel
-        const [canonical, disputed, alternative] = await Promise.all([
+        // Fetch events from all four timelines in parallel
+        const [pending, canonical, disputed, alternative] = await Promise.all([
+          getEventsByTimeline('pending'),
           getEventsByTimeline('canonical'),
           getEventsByTimeline('disputed'),
           getEventsByTimeline('alternative'),
         ]);
 
         // Merge all events into single array
-        const allEvents = [...canonical, ...disputed, ...alternative];
+        const allEvents = [...pending, ...canonical, ...disputed, ...alternative];
 
         // Sort chronologically by date
         allEvents.sort((a, b) => a.date.getTime() - b.date.getTime());
 
-        console.log(`getAllEvents: Retrieved ${allEvents.length} total events (${canonical.length} canonical, ${disputed.length} disputed, ${alternative.length} alternative)`);
+        console.log(`getAllEvents: Retrieved ${allEvents.length} total events (${pending.length} pending, ${canonical.length} canonical, ${disputed.length} disputed, ${alternative.length} alternative)`);
 
         return allEvents;
       },
@@ -624,6 +643,21 @@ export async function getAllEvents(): Promise<HistoricalEvent[]> {
   }
 }
 
+/**
+ * Helper function to parse an evidence source from contract response
+ * 
+ * @param source - Raw evidence source from contract
+ * @returns Evidence - Parsed evidence source
+ */
+function parseEvidence(source: any): Evidence {
+  return {
+    kind: source.kind,
+    citation: source.citation,
+    contentHash: source.contentHash ?? source.content_hash ?? null,
+    archivedCopy: source.archivedCopy ?? source.archived_copy ?? null,
+  };
+}
+
 /**
  * Helper function to parse event data from contract response
  * 
@@ -651,7 +685,7 @@ function parseEventData(eventData: any): HistoricalEvent | null {
       title: eventData.title,
       date: parseDateFromYYYYMMDD(cleanNumber(eventData.date)),
       description: eventData.description,
-      evidenceSources: eventData.evidenceSources || eventData.evidence_sources || [],
+      evidenceSources: (eventData.evidenceSources || eventData.evidence_sources || []).map(parseEvidence),
       tags: eventData.tags || [],
       submitter: eventData.submitter,
       timeline: eventData.timeline as Timeline,
@@ -5,13 +5,14 @@
 
 /**
  * Timeline categories for historical events
+ * New events stay 'pending' until they reach the contract's voting quorum
  */
-export type Timeline = 'canonical' | 'disputed' | 'alternative';
+export type Timeline = 'pending' | 'canonical' | 'disputed' | 'alternative';
 
 /**
  * Timeline view types including unified "all" view
  */
-export type TimelineViewType = 'all' | 'canonical' | 'disputed' | 'alternative';
+export type TimelineViewType = 'all' | 'pending' | 'canonical' | 'disputed' | 'alternative';
 
 /**
  * Timeline view configuration
@@ -28,11 +29,39 @@ export interface TimelineViewConfig {
  */
 export const TIMELINE_VIEWS: readonly TimelineViewConfig[] = [
   { id: 'all', label: 'All Events', icon: '📚' },
+  { id: 'pending', label: 'Pending', icon: '⏳', color: 'blue' },
   { id: 'canonical', label: 'Canonical', icon: '✓', color: 'green' },
   { id: 'disputed', label: 'Disputed', icon: '?', color: 'yellow' },
   { id: 'alternative', label: 'Alternative', icon: '⚠', color: 'red' },
 ] as const;
 
+/**
+ * Kinds of evidence source accepted by the contract
+ * Matches the EvidenceKind enum in the smart contract
+ */
+export type EvidenceKind = 'Url' | 'Isbn' | 'Doi' | 'Archive' | 'IpfsCid' | 'PrimaryDocument';
+
+/**
+ * A typed evidence source backing a historical event
+ * Matches the Evidence struct in the smart contract
+ */
+export interface Evidence {
+  kind: EvidenceKind;
+  citation: string;
+  contentHash: string | null; // Hex-encoded BLAKE2-256 hash
+  archivedCopy: string | null;
+}
+
+/**
+ * Raw evidence source as returned from the contract
+ */
+export interface RawEvidence {
+  kind: EvidenceKind;
+  citation: string;
+  content_hash: string | null;
+  archived_copy: string | null;
+}
+
 /**
  * Represents a historical event stored on-chain
  * Matches the HistoricalEvent struct in the smart contract
@@ -42,7 +71,7 @@ export interface HistoricalEvent {
   title: string;
   date: Date;
   description: string;
-  evidenceSources: string[];
+  evidenceSources: Evidence[];
   tags: string[];
   submitter: string;
   timeline: Timeline;
@@ -61,10 +90,10 @@ export interface RawHistoricalEvent {
   title: string;
   date: number; // Unix timestamp
   description: string;
-  evidence_sources: string[];
+  evidence_sources: RawEvidence[];
   tags: string[];
   submitter: string;
-  timeline: { canonical?: null; disputed?: null; alternative?: null };
+  timeline: { pending?: null; canonical?: null; disputed?: null; alternative?: null };
   consensus_score: number;
   support_votes: number;
   challenge_votes: number;
@@ -119,7 +148,55 @@ export interface ContractConfig {
 export enum ContractError {
   EventNotFound = 'EventNotFound',
   AlreadyVoted = 'AlreadyVoted',
-  InvalidEventData = 'InvalidEventData',
+  EmptyTitle = 'EmptyTitle',
+  EmptyDescription = 'EmptyDescription',
+  NoEvidence = 'NoEvidence',
+  EmptyEvidence = 'EmptyEvidence',
+  NoTags = 'NoTags',
+  TooManyTags = 'TooManyTags',
+  EmptyTag = 'EmptyTag',
+  DuplicateTag = 'DuplicateTag',
+  NotVoted = 'NotVoted',
+  VoteCooldown = 'VoteCooldown',
+  Unauthorized = 'Unauthorized',
+  InvalidConfig = 'InvalidConfig',
+  InsufficientStake = 'InsufficientStake',
+  UnexpectedValue = 'UnexpectedValue',
+  TransferFailed = 'TransferFailed',
+  WrongVotingMode = 'WrongVotingMode',
+  InvalidVoteCount = 'InvalidVoteCount',
+  InsufficientCredits = 'InsufficientCredits',
+  InsufficientDeposit = 'InsufficientDeposit',
+  TitleTooLong = 'TitleTooLong',
+  DescriptionTooLong = 'DescriptionTooLong',
+  EvidenceTooLong = 'EvidenceTooLong',
+  TagTooLong = 'TagTooLong',
+  NoDeposit = 'NoDeposit',
+  DepositAlreadySettled = 'DepositAlreadySettled',
+  ReviewWindowOpen = 'ReviewWindowOpen',
+  EventStillPending = 'EventStillPending',
+  EvidenceNotFound = 'EvidenceNotFound',
+  AlreadyAttested = 'AlreadyAttested',
+  Paused = 'Paused',
+  UpgradeFailed = 'UpgradeFailed',
+  MigrationPending = 'MigrationPending',
+  UnsupportedStorageVersion = 'UnsupportedStorageVersion',
+  InvalidEventId = 'InvalidEventId',
+  EventAlreadyExists = 'EventAlreadyExists',
+  BatchTooLarge = 'BatchTooLarge',
+  GracePeriodExpired = 'GracePeriodExpired',
+  AmendmentNotFound = 'AmendmentNotFound',
+  AmendmentClosed = 'AmendmentClosed',
+  InvalidUrl = 'InvalidUrl',
+  InvalidIsbn = 'InvalidIsbn',
+  InvalidDoi = 'InvalidDoi',
+  InvalidCid = 'InvalidCid',
+  TooManyEvidence = 'TooManyEvidence',
+  DuplicateEvidence = 'DuplicateEvidence',
+  EvidenceCooldown = 'EvidenceCooldown',
+  QueryTooBroad = 'QueryTooBroad',
+  SubstantiveEdit = 'SubstantiveEdit',
+  AmendmentStillOpen = 'AmendmentStillOpen',
 }
 
 /**